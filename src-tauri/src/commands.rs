@@ -0,0 +1,308 @@
+use std::collections::HashSet;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::time::Duration;
+use tauri::State;
+
+use crate::discovery::discover_games;
+use crate::models::{EmulatorSystem, Game, ThemeConfig, UserData};
+use crate::providers::{system_drives, ProviderRegistry};
+use crate::shell_link::{expand_env_vars, parse_lnk, parse_url_shortcut, resolve_relative};
+use crate::storage::{load_data, save_data};
+
+// Temps maximum d'un scan profond : au-delà, on renvoie ce qui a déjà été trouvé
+const DEEP_SCAN_BUDGET: Duration = Duration::from_secs(45);
+
+pub struct AppState {
+    pub data_path: PathBuf,
+    pub data: Mutex<UserData>,
+    pub providers: ProviderRegistry,
+}
+
+impl AppState {
+    /// Charge les données sauvegardées et enregistre toutes les boutiques par défaut.
+    pub fn new(data_path: PathBuf) -> Self {
+        let initial_data = load_data(&data_path);
+        Self {
+            data_path,
+            data: Mutex::new(initial_data),
+            providers: ProviderRegistry::with_defaults(),
+        }
+    }
+}
+
+// ===================== LOGIQUE DE LA BIBLIOTHÈQUE =====================
+
+/// Construit la liste finale affichée par le Hub à partir des jeux scannés :
+/// filtre par disque et masque les outils, ajoute les jeux Custom, applique les favoris, dédoublonne et trie.
+pub fn build_library(all_scanned_games: Vec<Game>, data: &UserData) -> Vec<Game> {
+    // 1. On filtre selon les disques sélectionnés
+    let mut filtered_games = Vec::new();
+    let use_filter = !data.selected_drives.is_empty();
+    
+    for game in all_scanned_games {
+        // Les outils (Proton, redistribuables...) ne sont affichés que sur demande
+        if game.is_tool && !data.show_tools {
+            continue;
+        }
+        if use_filter {
+            // FIX: On remplace tous les / par des \ pour normaliser la comparaison
+            // Cela règle le problème des jeux Ubisoft qui utilisent souvent des "/"
+            let game_drive_norm = game.install_dir.to_lowercase().replace("/", "\\");
+            
+            let match_found = data.selected_drives.iter().any(|d| {
+                let selected_drive_norm = d.to_lowercase().replace("/", "\\");
+                game_drive_norm.starts_with(&selected_drive_norm)
+            });
+            
+            if !match_found && !game.install_dir.is_empty() {
+                // Si le jeu n'est pas sur le disque sélectionné, on l'ignore
+                continue; 
+            }
+        }
+        filtered_games.push(game);
+    }
+
+    // 2. On ajoute les jeux Custom (qui ne sont pas filtrés par disque pour éviter de les perdre)
+    filtered_games.extend(data.custom_games.clone());
+
+    // 3. Gestion des favoris et dédoublonnage
+    let mut unique_games = Vec::new();
+    let mut seen_ids = HashSet::new();
+
+    for mut game in filtered_games {
+        let unique_key = format!("{}-{}", game.platform, game.id);
+        if data.favorites.contains(&unique_key) {
+            game.is_favorite = true;
+        }
+        if !seen_ids.contains(&unique_key) {
+            seen_ids.insert(unique_key);
+            unique_games.push(game);
+        }
+    }
+
+    unique_games.sort_by_key(|g| g.title.to_lowercase());
+    unique_games
+}
+
+/// Jeux Custom proposés à partir des raccourcis (.lnk / .url) trouvés dans les dossiers donnés
+/// (sous-dossiers compris, comme le menu Démarrer). Les désinstalleurs, manuels et liens web sont ignorés.
+pub fn shortcut_proposals(folders: &[String]) -> Vec<Game> {
+    let mut files = Vec::new();
+    for folder in folders {
+        collect_shortcut_files(Path::new(folder), 0, &mut files);
+    }
+
+    let mut proposals: Vec<Game> = Vec::new();
+    for file in files {
+        let Some(game) = shortcut_to_game(&file) else { continue };
+        let duplicate = proposals.iter().any(|p| p.exe_path == game.exe_path && p.launch_options == game.launch_options);
+        if !duplicate {
+            proposals.push(game);
+        }
+    }
+    proposals.sort_by_key(|g| g.title.to_lowercase());
+    proposals
+}
+
+fn collect_shortcut_files(dir: &Path, depth: usize, files: &mut Vec<PathBuf>) {
+    let Ok(entries) = fs::read_dir(dir) else { return };
+    for entry in entries.flatten() {
+        let path = entry.path();
+        if path.is_dir() {
+            if depth < 3 {
+                collect_shortcut_files(&path, depth + 1, files);
+            }
+        } else if path.extension().is_some_and(|e| e.eq_ignore_ascii_case("lnk") || e.eq_ignore_ascii_case("url")) {
+            files.push(path);
+        }
+    }
+}
+
+fn shortcut_to_game(file: &Path) -> Option<Game> {
+    let title = file.file_stem()?.to_string_lossy().to_string();
+    let is_image = |path: &str| {
+        let lower = path.to_lowercase();
+        [".png", ".jpg", ".jpeg", ".webp", ".ico"].iter().any(|ext| lower.ends_with(ext))
+    };
+
+    let is_url = file.extension().is_some_and(|e| e.eq_ignore_ascii_case("url"));
+    let game = if is_url {
+        let shortcut = parse_url_shortcut(&fs::read_to_string(file).ok()?)?;
+        // Les liens vers des sites web ne sont pas des jeux (les URL steam://, uplay://... le sont)
+        let lower_url = shortcut.url.to_lowercase();
+        if lower_url.starts_with("http://") || lower_url.starts_with("https://") { return None; }
+        let icon = expand_env_vars(&shortcut.icon_file);
+        Game {
+            title,
+            platform: "Custom".to_string(),
+            image_path: if is_image(&icon) { icon } else { "".to_string() },
+            exe_path: shortcut.url,
+            ..Default::default()
+        }
+    } else {
+        let link = parse_lnk(&fs::read(file).ok()?).ok()?;
+        let target = if !link.target.is_empty() {
+            expand_env_vars(&link.target)
+        } else if !link.relative_path.is_empty() {
+            resolve_relative(file, &link.relative_path)
+        } else {
+            // Raccourci "publié" (Windows Installer) : seul le .lnk lui-même sait lancer le jeu
+            file.to_string_lossy().to_string()
+        };
+        let icon = expand_env_vars(&link.icon_location);
+        Game {
+            title,
+            platform: "Custom".to_string(),
+            image_path: if is_image(&icon) { icon } else { "".to_string() },
+            exe_path: target,
+            launch_options: link.arguments,
+            install_dir: expand_env_vars(&link.working_dir),
+            ..Default::default()
+        }
+    };
+
+    let target_name = Path::new(&game.exe_path.replace('\\', "/")).file_name()?.to_string_lossy().to_lowercase();
+    let lower_title = game.title.to_lowercase();
+    let ignored = ["unins", "uninstall", "setup", "readme", "manual"].iter().any(|w| target_name.contains(w) || lower_title.contains(w))
+        || [".txt", ".pdf", ".htm", ".html", ".chm", ".rtf"].iter().any(|ext| target_name.ends_with(ext));
+    if ignored { None } else { Some(game) }
+}
+
+// ===================== COMMANDES TAURI =====================
+
+// launch_options et working_dir sont remplis quand le jeu vient d'un raccourci (arguments, dossier de travail)
+#[tauri::command]
+pub fn add_custom_game(title: String, exe_path: String, image_path: String, launch_options: Option<String>, working_dir: Option<String>, state: State<AppState>) -> Result<(), String> {
+    let mut data = state.data.lock().map_err(|_| "Lock error")?;
+    let now = std::time::SystemTime::now().duration_since(std::time::UNIX_EPOCH).unwrap();
+    // Plusieurs jeux peuvent être ajoutés dans la même seconde (import de raccourcis) : suffixe -2, -3...
+    let base_id = format!("Custom-{}", now.as_secs());
+    let mut id = base_id.clone();
+    let mut suffix = 1;
+    while data.custom_games.iter().any(|g| g.id == id) {
+        suffix += 1;
+        id = format!("{}-{}", base_id, suffix);
+    }
+    
+    let new_game = Game {
+        id, title, platform: "Custom".to_string(), image_path, exe_path,
+        install_dir: working_dir.unwrap_or_default(), launch_options: launch_options.unwrap_or_default(),
+        is_favorite: false, ..Default::default()
+    };
+
+    data.custom_games.push(new_game);
+    save_data(&state.data_path, &data);
+    Ok(())
+}
+
+#[tauri::command]
+pub fn find_shortcut_games(folders: Vec<String>) -> Vec<Game> {
+    shortcut_proposals(&folders)
+}
+
+// Scan profond à la demande : les dossiers déclarés, sinon les disques sélectionnés (ou tous).
+// "async" pour que le parcours des disques ne bloque pas la fenêtre.
+#[tauri::command(async)]
+pub fn find_deep_scan_games(state: State<'_, AppState>) -> Result<Vec<Game>, String> {
+    let data = state.data.lock().map_err(|_| "Lock error")?.clone();
+    let roots: Vec<PathBuf> = if !data.deep_scan_folders.is_empty() {
+        data.deep_scan_folders.iter().map(PathBuf::from).collect()
+    } else if !data.selected_drives.is_empty() {
+        data.selected_drives.iter().map(PathBuf::from).collect()
+    } else {
+        system_drives().into_iter().map(PathBuf::from).collect()
+    };
+
+    let known: HashSet<String> = data.custom_games.iter().map(|g| g.exe_path.to_lowercase()).collect();
+    Ok(discover_games(&roots, DEEP_SCAN_BUDGET)
+        .into_iter()
+        .filter(|g| !known.contains(&g.exe_path.to_lowercase()))
+        .collect())
+}
+
+#[tauri::command]
+pub fn toggle_favorite(game_id: String, platform: String, state: State<AppState>) -> Result<bool, String> {
+    let mut data = state.data.lock().map_err(|_| "Lock error")?;
+    let key = format!("{}-{}", platform, game_id);
+    
+    let is_fav = if data.favorites.contains(&key) {
+        data.favorites.remove(&key);
+        false
+    } else {
+        data.favorites.insert(key);
+        true
+    };
+    save_data(&state.data_path, &data);
+    Ok(is_fav)
+}
+
+#[tauri::command]
+pub fn get_system_drives() -> Vec<String> {
+    system_drives()
+}
+
+// Sauvegarder les paramètres complets (Theme + Disques + Outils + Émulateurs)
+// show_tools et emulator_systems sont optionnels pour rester compatibles avec les anciens appels du frontend
+#[tauri::command]
+pub fn update_settings(theme: ThemeConfig, selected_drives: Vec<String>, show_tools: Option<bool>, emulator_systems: Option<Vec<EmulatorSystem>>, deep_scan_folders: Option<Vec<String>>, state: State<AppState>) -> Result<(), String> {
+    if let Some(emulator_systems) = &emulator_systems {
+        state.providers.check_emulator_systems(emulator_systems)?;
+    }
+    let mut data = state.data.lock().map_err(|_| "Lock error")?;
+    data.theme = theme;
+    data.selected_drives = selected_drives.into_iter().collect();
+    if let Some(show_tools) = show_tools {
+        data.show_tools = show_tools;
+    }
+    if let Some(emulator_systems) = emulator_systems {
+        data.emulator_systems = emulator_systems;
+    }
+    if let Some(deep_scan_folders) = deep_scan_folders {
+        data.deep_scan_folders = deep_scan_folders;
+    }
+    save_data(&state.data_path, &data);
+    Ok(())
+}
+
+#[tauri::command]
+pub fn get_settings(state: State<AppState>) -> Result<UserData, String> {
+    let data = state.data.lock().map_err(|_| "Lock error")?;
+    Ok(data.clone())
+}
+
+#[tauri::command]
+pub fn get_games(state: State<AppState>) -> Vec<Game> {
+    // On copie les réglages pour ne pas garder le verrou pendant le scan
+    let mut data = state.data.lock().unwrap().clone();
+
+    // 1. On récupère tout (chaque boutique est un provider du registre)
+    let all_scanned_games = state.providers.scan_all(&data);
+
+    // Favoris enregistrés avec un ancien id (ex: EA) : repris une fois sous l'id actuel
+    if let Ok(mut saved) = state.data.lock() {
+        if state.providers.migrate_favorites(&all_scanned_games, &mut saved.favorites) {
+            save_data(&state.data_path, &saved);
+            data.favorites = saved.favorites.clone();
+        }
+    }
+
+    build_library(all_scanned_games, &data)
+}
+
+#[tauri::command]
+pub fn launch_game(id: String, platform: String, exe_path: String, state: State<AppState>) {
+    let data = state.data.lock().unwrap().clone();
+    if let Some(provider) = state.providers.get_for(&platform, &data) {
+        provider.launch_with_settings(&id, &exe_path, &data);
+    }
+}
+
+#[tauri::command]
+pub fn open_launcher_page(id: String, platform: String, state: State<AppState>) {
+    let data = state.data.lock().unwrap().clone();
+    if let Some(provider) = state.providers.get_for(&platform, &data) {
+        provider.open_store_page(&id);
+    }
+}
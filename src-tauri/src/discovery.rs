@@ -0,0 +1,432 @@
+use std::collections::VecDeque;
+use std::fs::{self, File};
+use std::io::{Read, Seek, SeekFrom};
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
+
+use crate::models::Game;
+use crate::providers::scavenge_image;
+
+// ===================== SCAN PROFOND (JEUX SANS LAUNCHER) =====================
+// Les jeux DRM-free décompressés dans un dossier (D:\Games\...) ne sont connus d'aucune boutique.
+// Sur demande, on parcourt les disques ou dossiers choisis et on reconnaît un dossier de jeu
+// à sa structure : moteur Unity (UnityPlayer.dll + <Nom>_Data), Unreal (Engine\ + Binaries\Win64),
+// Godot (.pck), GameMaker (data.win), Ren'Py, ou bibliothèques typiques des jeux (steam_api, bink, fmod...).
+// Le titre vient du ProductName de l'exécutable (ressource VERSIONINFO), sinon du nom du dossier.
+// Un dossier reconnu n'est pas parcouru plus loin ; le parcours s'arrête quand le budget de temps est écoulé.
+
+// Profondeur max sous chaque racine (D:\Games\Editeur\Jeu\Binaries\Win64 reste atteignable)
+const MAX_DEPTH: usize = 6;
+
+// Dossiers système ou sans jeux : jamais parcourus (comparaison insensible à la casse)
+// (steamapps et XboxGames sont déjà couverts par leur provider)
+const SKIPPED_DIRS: [&str; 24] = [
+    "windows", "$recycle.bin", "system volume information", "recovery", "perflogs", "config.msi",
+    "msocache", "programdata", "appdata", "windowsapps", "common files", "windows kits",
+    "windows defender", "windows nt", "microsoft", "microsoft office", "nvidia corporation",
+    "intel", "amd", "drivers", "node_modules", "__pycache__", "steamapps", "xboxgames",
+];
+
+// Dossiers système à la racine d'un Linux / macOS
+const UNIX_ROOT_DIRS: [&str; 17] = [
+    "proc", "sys", "dev", "run", "usr", "etc", "bin", "sbin", "lib", "lib32", "lib64", "boot",
+    "var", "tmp", "snap", "nix", "System",
+];
+
+// Exécutables qui ne sont jamais le jeu lui-même
+const IGNORED_EXE_WORDS: [&str; 14] = [
+    "unins", "setup", "install", "redist", "vcredist", "vc_redist", "dxsetup", "crash",
+    "report", "update", "helper", "uploader", "cleanup", "dotnet",
+];
+
+// Bibliothèques que l'on ne trouve pratiquement que dans des jeux
+const GAME_LIBRARIES: [&str; 14] = [
+    "steam_api.dll", "steam_api64.dll", "galaxy.dll", "galaxy64.dll", "eossdk-win64-shipping.dll",
+    "binkw32.dll", "binkw64.dll", "bink2w64.dll", "fmod.dll", "fmod64.dll", "fmodex.dll",
+    "openal32.dll", "physxloader.dll", "d3dx9_43.dll",
+];
+
+// ProductName génériques (moteur, runtime) : on prend alors le nom du dossier
+const GENERIC_PRODUCT_NAMES: [&str; 8] = [
+    "unity", "unreal engine", "ue4", "ue5", "bootstrappackagedgame", "godot engine", "nw.js", "electron",
+];
+
+/// Jeux Custom proposés à partir des dossiers donnés, dans la limite du budget de temps.
+pub fn discover_games(roots: &[PathBuf], budget: Duration) -> Vec<Game> {
+    let deadline = Instant::now() + budget;
+    let mut queue: VecDeque<(PathBuf, usize)> = roots.iter().map(|r| (r.clone(), 0)).collect();
+    let mut games: Vec<Game> = Vec::new();
+
+    // Parcours en largeur : si le budget est dépassé, les dossiers proches des racines ont été vus
+    while let Some((dir, depth)) = queue.pop_front() {
+        if Instant::now() >= deadline { break; }
+        let Ok(entries) = fs::read_dir(&dir) else { continue };
+        let mut files = Vec::new();
+        let mut subdirs = Vec::new();
+        for entry in entries.flatten() {
+            let path = entry.path();
+            // file_type() ne suit pas les liens : pas de boucle par un lien symbolique ou une jonction
+            match entry.file_type() {
+                Ok(t) if t.is_dir() => subdirs.push(path),
+                Ok(t) if t.is_file() => files.push(path),
+                _ => {}
+            }
+        }
+
+        if let Some(game) = detect_game(&dir, &files, &subdirs) {
+            if !games.iter().any(|g| g.exe_path == game.exe_path) {
+                games.push(game);
+            }
+            continue;
+        }
+        if depth < MAX_DEPTH {
+            queue.extend(subdirs.into_iter().filter(|d| !is_skipped_dir(d)).map(|d| (d, depth + 1)));
+        }
+    }
+    games.sort_by_key(|g| g.title.to_lowercase());
+    games
+}
+
+fn is_skipped_dir(dir: &Path) -> bool {
+    let name = dir.file_name().unwrap_or_default().to_string_lossy().to_string();
+    let at_unix_root = dir.parent() == Some(Path::new("/"));
+    name.starts_with('.')
+        || SKIPPED_DIRS.contains(&name.to_lowercase().as_str())
+        || (at_unix_root && UNIX_ROOT_DIRS.contains(&name.as_str()))
+}
+
+fn lower_name(path: &Path) -> String {
+    path.file_name().unwrap_or_default().to_string_lossy().to_lowercase()
+}
+
+fn is_candidate_exe(path: &Path) -> bool {
+    let name = lower_name(path);
+    name.ends_with(".exe") && !IGNORED_EXE_WORDS.iter().any(|w| name.contains(w))
+}
+
+/// Le jeu contenu dans ce dossier, si sa structure est celle d'un jeu.
+/// `files` et `subdirs` sont le contenu direct du dossier.
+pub fn detect_game(dir: &Path, files: &[PathBuf], subdirs: &[PathBuf]) -> Option<Game> {
+    let exes: Vec<&PathBuf> = files.iter().filter(|f| is_candidate_exe(f)).collect();
+    let has_file = |name: &str| files.iter().any(|f| lower_name(f) == name);
+    let has_subdir = |name: &str| subdirs.iter().any(|d| lower_name(d) == name);
+    let exe_with_stem = |stem: &str| exes.iter().find(|e| e.file_stem().is_some_and(|s| s.to_string_lossy().eq_ignore_ascii_case(stem))).map(|e| e.to_path_buf());
+
+    // Unity : <Nom>.exe à côté de <Nom>_Data
+    let unity = has_file("unityplayer.dll")
+        .then(|| {
+            subdirs.iter().find_map(|d| {
+                let name = d.file_name()?.to_string_lossy().to_string();
+                exe_with_stem(name.strip_suffix("_Data")?)
+            })
+        })
+        .flatten();
+
+    // Unreal (jeu packagé, toujours avec son dossier Engine) : l'exécutable racine (lanceur),
+    // sinon <Projet>\Binaries\Win64\*.exe
+    let unreal = || {
+        if !has_subdir("engine") { return None; }
+        best_exe(dir, &exes).or_else(|| {
+            subdirs.iter().filter(|d| lower_name(d) != "engine").find_map(|d| {
+                let win64 = d.join("Binaries").join("Win64");
+                let mut shipping: Vec<PathBuf> = fs::read_dir(win64).ok()?.flatten().map(|e| e.path()).filter(|p| is_candidate_exe(p)).collect();
+                shipping.sort();
+                shipping.into_iter().next()
+            })
+        })
+    };
+
+    // Godot (<Nom>.pck), GameMaker (data.win), Ren'Py (dossier renpy)
+    let packaged = || {
+        files
+            .iter()
+            .filter(|f| lower_name(f).ends_with(".pck"))
+            .find_map(|pck| exe_with_stem(&pck.file_stem()?.to_string_lossy()))
+            .or_else(|| (has_file("data.win") || has_subdir("renpy")).then(|| best_exe(dir, &exes)).flatten())
+    };
+
+    // Bibliothèques de jeux à côté d'un exécutable
+    let generic = || GAME_LIBRARIES.iter().any(|lib| has_file(lib)).then(|| best_exe(dir, &exes)).flatten();
+
+    let exe = unity.or_else(unreal).or_else(packaged).or_else(generic)?;
+    let folder_title = dir.file_name()?.to_string_lossy().to_string();
+    let title = pe_product_name(&exe)
+        .filter(|name| !GENERIC_PRODUCT_NAMES.contains(&name.to_lowercase().as_str()))
+        .unwrap_or(folder_title);
+    let install_dir = dir.to_string_lossy().to_string();
+
+    Some(Game {
+        title,
+        platform: "Custom".to_string(),
+        image_path: scavenge_image(&install_dir),
+        exe_path: exe.to_string_lossy().to_string(),
+        install_dir,
+        ..Default::default()
+    })
+}
+
+// Sans indice du moteur : l'exécutable au nom du dossier, sinon le plus gros
+fn best_exe(dir: &Path, exes: &[&PathBuf]) -> Option<PathBuf> {
+    let folder = dir.file_name()?.to_string_lossy().to_lowercase().replace([' ', '-', '_'], "");
+    let matching = exes.iter().find(|e| {
+        let stem = e.file_stem().unwrap_or_default().to_string_lossy().to_lowercase().replace([' ', '-', '_'], "");
+        !stem.is_empty() && (folder.contains(&stem) || stem.contains(&folder))
+    });
+    matching
+        .or_else(|| exes.iter().max_by_key(|e| e.metadata().map(|m| m.len()).unwrap_or(0)))
+        .map(|e| e.to_path_buf())
+}
+
+// ===================== VERSIONINFO DES EXÉCUTABLES (PE) =====================
+// En-tête DOS -> en-tête PE -> table des sections -> répertoire de ressources (data directory n°2)
+// -> ressource RT_VERSION (16) -> VS_VERSIONINFO, dont la StringTable contient "ProductName".
+// On ne lit que les octets nécessaires : certains exécutables font plusieurs Go.
+
+const RT_VERSION: u32 = 16;
+
+/// ProductName de la ressource de version d'un exécutable Windows (None si absente).
+pub fn pe_product_name(exe: &Path) -> Option<String> {
+    let version_info = read_version_resource(&mut File::open(exe).ok()?)?;
+    let name = version_string(&version_info, "ProductName").or_else(|| version_string(&version_info, "FileDescription"))?;
+    let name = name.trim().to_string();
+    if name.is_empty() { None } else { Some(name) }
+}
+
+fn read_at(file: &mut File, offset: u64, len: usize) -> Option<Vec<u8>> {
+    file.seek(SeekFrom::Start(offset)).ok()?;
+    let mut buf = vec![0u8; len];
+    file.read_exact(&mut buf).ok()?;
+    Some(buf)
+}
+
+fn le_u16(b: &[u8], at: usize) -> Option<u16> {
+    Some(u16::from_le_bytes(b.get(at..at + 2)?.try_into().ok()?))
+}
+
+fn le_u32(b: &[u8], at: usize) -> Option<u32> {
+    Some(u32::from_le_bytes(b.get(at..at + 4)?.try_into().ok()?))
+}
+
+fn read_version_resource(file: &mut File) -> Option<Vec<u8>> {
+    let dos = read_at(file, 0, 0x40)?;
+    if dos.get(0..2)? != b"MZ" { return None; }
+    let pe_offset = le_u32(&dos, 0x3C)? as u64;
+
+    let headers = read_at(file, pe_offset, 24)?;
+    if headers.get(0..4)? != b"PE\0\0" { return None; }
+    let section_count = le_u16(&headers, 6)? as usize;
+    let optional_size = le_u16(&headers, 20)? as usize;
+    let optional = read_at(file, pe_offset + 24, optional_size)?;
+    // PE32 (0x10B) ou PE32+ (0x20B) : les data directories ne commencent pas au même endroit
+    let directories = match le_u16(&optional, 0)? {
+        0x10B => 96,
+        0x20B => 112,
+        _ => return None,
+    };
+    let resource_rva = le_u32(&optional, directories + 2 * 8)?;
+    if resource_rva == 0 { return None; }
+
+    let sections = read_at(file, pe_offset + 24 + optional_size as u64, section_count * 40)?;
+    let rva_to_offset = |rva: u32| -> Option<u64> {
+        sections.chunks_exact(40).find_map(|s| {
+            let virtual_size = le_u32(s, 8)?.max(le_u32(s, 16)?);
+            let virtual_address = le_u32(s, 12)?;
+            let raw_pointer = le_u32(s, 20)?;
+            // Valeurs lues dans le fichier : un en-tête forgé ne doit pas faire déborder le calcul
+            let end = virtual_address.checked_add(virtual_size)?;
+            if rva < virtual_address || rva >= end { return None; }
+            (rva - virtual_address).checked_add(raw_pointer).map(u64::from)
+        })
+    };
+    let resource_base = rva_to_offset(resource_rva)?;
+
+    // Niveau 1 : type (RT_VERSION), niveau 2 : id, niveau 3 : langue -> données
+    let mut entry = find_resource_entry(file, resource_base, 0, Some(RT_VERSION))?;
+    for _ in 0..2 {
+        if entry & 0x8000_0000 == 0 { break; }
+        entry = find_resource_entry(file, resource_base, entry & 0x7FFF_FFFF, None)?;
+    }
+    if entry & 0x8000_0000 != 0 { return None; }
+
+    let data_entry = read_at(file, resource_base + entry as u64, 8)?;
+    let size = (le_u32(&data_entry, 4)? as usize).min(64 * 1024);
+    read_at(file, rva_to_offset(le_u32(&data_entry, 0)?)?, size)
+}
+
+// Entrée d'un répertoire de ressources : celle qui a l'id voulu, ou la première
+fn find_resource_entry(file: &mut File, base: u64, dir_offset: u32, id: Option<u32>) -> Option<u32> {
+    let header = read_at(file, base + dir_offset as u64, 16)?;
+    let count = le_u16(&header, 12)? as usize + le_u16(&header, 14)? as usize;
+    let entries = read_at(file, base + dir_offset as u64 + 16, count.min(4096) * 8)?;
+    entries.chunks_exact(8).find_map(|e| {
+        let name = le_u32(e, 0)?;
+        let matches = match id {
+            Some(id) => name == id,
+            None => true,
+        };
+        if matches { le_u32(e, 4) } else { None }
+    })
+}
+
+/// Valeur d'une chaîne de la StringTable d'un VS_VERSIONINFO (clé puis valeur, UTF-16, alignées sur 4 octets).
+pub fn version_string(version_info: &[u8], key: &str) -> Option<String> {
+    let units: Vec<u16> = version_info.chunks_exact(2).map(|c| u16::from_le_bytes([c[0], c[1]])).collect();
+    let mut needle: Vec<u16> = key.encode_utf16().collect();
+    needle.push(0);
+
+    let start = units.windows(needle.len()).position(|w| w == needle.as_slice())?;
+    // Alignement sur 4 octets (2 unités UTF-16) à partir du début du bloc
+    let value_start = start + needle.len();
+    let value_start = value_start + value_start % 2;
+    let value: Vec<u16> = units.get(value_start..)?.iter().copied().take_while(|&u| u != 0).collect();
+    Some(String::from_utf16_lossy(&value))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Section (adresse virtuelle, taille virtuelle, position dans le fichier)
+    type Section = (u32, u32, u32);
+
+    const RESOURCES: Section = (0x1000, 0x1000, 0x200);
+
+    // Exécutable PE32+ minimal : une section de ressources contenant un VS_VERSIONINFO réduit
+    // à la chaîne ProductName (répertoires type -> id -> langue -> données)
+    fn pe(section: Section, resource_rva: u32, product_name: &str) -> Vec<u8> {
+        let (virtual_address, virtual_size, raw_pointer) = section;
+        let mut data = vec![0u8; 0x200];
+        data[..2].copy_from_slice(b"MZ");
+        data[0x3C..0x40].copy_from_slice(&0x40u32.to_le_bytes());
+        data[0x40..0x44].copy_from_slice(b"PE\0\0");
+        data[0x46..0x48].copy_from_slice(&1u16.to_le_bytes());
+        data[0x54..0x56].copy_from_slice(&240u16.to_le_bytes());
+        let optional = 0x58;
+        data[optional..optional + 2].copy_from_slice(&0x20Bu16.to_le_bytes());
+        data[optional + 128..optional + 132].copy_from_slice(&resource_rva.to_le_bytes());
+        let header = optional + 240;
+        for (at, value) in [(8, virtual_size), (12, virtual_address), (16, virtual_size), (20, raw_pointer)] {
+            data[header + at..header + at + 4].copy_from_slice(&value.to_le_bytes());
+        }
+
+        let mut resources = Vec::new();
+        for (id, target) in [(RT_VERSION, 0x8000_0018u32), (1, 0x8000_0030), (0x409, 0x48)] {
+            resources.extend([0u8; 14]);
+            resources.extend(1u16.to_le_bytes());
+            resources.extend(id.to_le_bytes());
+            resources.extend(target.to_le_bytes());
+        }
+        let mut units: Vec<u16> = vec![0, 0, 0, 0, 0, 0, 1];
+        units.extend("ProductName\0".encode_utf16());
+        if units.len() % 2 == 1 { units.push(0); }
+        units.extend(product_name.encode_utf16().chain([0]));
+        let version_info: Vec<u8> = units.iter().flat_map(|u| u.to_le_bytes()).collect();
+        resources.extend((virtual_address.wrapping_add(0x58)).to_le_bytes());
+        resources.extend((version_info.len() as u32).to_le_bytes());
+        resources.extend([0u8; 8]);
+        resources.extend(version_info);
+        data.extend(resources);
+        data
+    }
+
+    fn temp_tree(name: &str) -> PathBuf {
+        let root = std::env::temp_dir().join(format!("geewers-discovery-{}-{}", name, std::process::id()));
+        fs::remove_dir_all(&root).ok();
+        fs::create_dir_all(&root).unwrap();
+        root
+    }
+
+    fn touch(path: &Path, content: &[u8]) {
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        fs::write(path, content).unwrap();
+    }
+
+    #[test]
+    fn reads_the_product_name() {
+        let root = temp_tree("pe");
+        let exe = root.join("game.exe");
+        touch(&exe, &pe(RESOURCES, 0x1000, "Hollow Knight"));
+        assert_eq!(pe_product_name(&exe).as_deref(), Some("Hollow Knight"));
+        fs::remove_dir_all(&root).ok();
+    }
+
+    #[test]
+    fn survives_truncated_and_forged_executables() {
+        let root = temp_tree("forged");
+        let exe = root.join("game.exe");
+        let valid = pe(RESOURCES, 0x1000, "Hollow Knight");
+        for len in [0, 2, 0x40, 0x100, 0x250, valid.len() - 4] {
+            touch(&exe, &valid[..len]);
+            assert_eq!(pe_product_name(&exe), None, "longueur {}", len);
+        }
+        // Section dont la fin dépasse 4 Go, puis section dont la position dans le fichier déborde
+        touch(&exe, &pe((0xFFFF_F000, 0x2000, 0x200), 0xFFFF_F800, "X"));
+        assert_eq!(pe_product_name(&exe), None);
+        touch(&exe, &pe((0x1000, 0x1000, 0xFFFF_FF00), 0x1F00, "X"));
+        assert_eq!(pe_product_name(&exe), None);
+        // En-tête PE hors du fichier
+        let mut far = valid.clone();
+        far[0x3C..0x40].copy_from_slice(&u32::MAX.to_le_bytes());
+        touch(&exe, &far);
+        assert_eq!(pe_product_name(&exe), None);
+        fs::remove_dir_all(&root).ok();
+    }
+
+    #[test]
+    fn detects_engines_from_the_folder_content() {
+        let dir = Path::new("/jeux/Hollow Knight");
+        let unity_files = [dir.join("UnityPlayer.dll"), dir.join("UnityCrashHandler64.exe"), dir.join("hollow_knight.exe")];
+        let game = detect_game(dir, &unity_files, &[dir.join("hollow_knight_Data")]).unwrap();
+        assert_eq!(game.exe_path, dir.join("hollow_knight.exe").to_string_lossy());
+        assert_eq!(game.title, "Hollow Knight");
+        assert_eq!(game.platform, "Custom");
+        // Unity sans <Nom>_Data correspondant, ou simple dossier d'outils : pas un jeu
+        assert!(detect_game(dir, &unity_files, &[dir.join("Data")]).is_none());
+        assert!(detect_game(dir, &[dir.join("tool.exe"), dir.join("readme.txt")], &[]).is_none());
+
+        // Bibliothèque de jeu : l'exécutable au nom du dossier, pas le désinstalleur
+        let dir = Path::new("/jeux/Celeste");
+        let files = [dir.join("unins000.exe"), dir.join("fmod.dll"), dir.join("Celeste.exe"), dir.join("Celeste Editor.exe")];
+        assert_eq!(detect_game(dir, &files, &[]).unwrap().exe_path, dir.join("Celeste.exe").to_string_lossy());
+
+        // Godot : l'exécutable qui porte le nom du .pck
+        let dir = Path::new("/jeux/Brotato");
+        let files = [dir.join("Launcher.exe"), dir.join("Brotato.exe"), dir.join("Brotato.pck")];
+        assert_eq!(detect_game(dir, &files, &[]).unwrap().exe_path, dir.join("Brotato.exe").to_string_lossy());
+    }
+
+    #[test]
+    fn discovers_games_and_skips_system_folders() {
+        let root = temp_tree("scan");
+        let hollow = root.join("Games").join("Team Cherry").join("Hollow Knight");
+        touch(&hollow.join("UnityPlayer.dll"), b"");
+        touch(&hollow.join("hollow_knight.exe"), &pe(RESOURCES, 0x1000, "Hollow Knight"));
+        fs::create_dir_all(hollow.join("hollow_knight_Data")).unwrap();
+        // Un dossier reconnu n'est pas parcouru plus loin
+        touch(&hollow.join("Mods").join("Other").join("steam_api64.dll"), b"");
+        touch(&hollow.join("Mods").join("Other").join("Other.exe"), b"");
+
+        // Exécutable tronqué : le titre vient du dossier
+        let celeste = root.join("Games").join("Celeste");
+        touch(&celeste.join("fmod.dll"), b"");
+        touch(&celeste.join("Celeste.exe"), b"MZ\x90\x00");
+
+        for skipped in ["AppData", "Windows", ".cache", "node_modules"] {
+            let dir = root.join(skipped).join("Game");
+            touch(&dir.join("steam_api.dll"), b"");
+            touch(&dir.join("Game.exe"), b"");
+        }
+
+        let games = discover_games(std::slice::from_ref(&root), Duration::from_secs(30));
+        let found: Vec<(&str, &str)> = games.iter().map(|g| (g.title.as_str(), g.install_dir.as_str())).collect();
+        assert_eq!(
+            found,
+            vec![
+                ("Celeste", celeste.to_str().unwrap()),
+                ("Hollow Knight", hollow.to_str().unwrap()),
+            ]
+        );
+        assert!(discover_games(std::slice::from_ref(&root), Duration::ZERO).is_empty());
+        fs::remove_dir_all(&root).ok();
+    }
+}
@@ -1,4 +1,19 @@
-// Ce fichier est vide car tout le code est dans main.rs
-pub fn run() {
-    println!("Démarrage via main.rs");
-}
\ No newline at end of file
+// Cœur du Hub : modèles, sauvegarde, scanners (providers) et commandes Tauri.
+// Le binaire (main.rs) ne fait que brancher ces commandes dans Tauri, ce qui permet
+// de réutiliser les scanners depuis nos propres outils et depuis les tests d'intégration.
+
+pub mod commands;
+pub mod discovery;
+pub mod models;
+pub mod protobuf;
+pub mod providers;
+pub mod registry;
+pub mod shell_link;
+pub mod storage;
+pub mod vdf;
+pub mod web;
+
+pub use commands::{build_library, AppState};
+pub use models::{EmulatorSystem, Game, HltbTime, InstallState, ModInfo, ThemeConfig, UserData};
+pub use providers::{GameProvider, ProviderRegistry};
+pub use registry::{Hive, MemoryRegistry, RegistryReader};
@@ -0,0 +1,115 @@
+use std::collections::HashSet;
+use serde::{Serialize, Deserialize};
+
+// ===================== STRUCTURES DE DONNÉES =====================
+
+#[derive(Serialize, Deserialize, Clone, Debug, Default)]
+pub struct Game {
+    pub id: String,
+    pub title: String,
+    pub platform: String,
+    pub image_path: String,
+    pub exe_path: String,
+    pub install_dir: String,
+    pub is_favorite: bool,
+
+    // Champs ajoutés après la v1 : #[serde(default)] pour relire les jeux Custom déjà sauvegardés
+    #[serde(default)]
+    pub launch_options: String,
+    #[serde(default)]
+    pub playtime_minutes: u64, // Temps de jeu total connu par le launcher
+    #[serde(default)]
+    pub last_played: u64,      // Timestamp Unix (secondes) de la dernière session, 0 si inconnu
+    #[serde(default)]
+    pub is_tool: bool,         // Outil, runtime ou redistribuable : masqué sauf si UserData.show_tools
+    #[serde(default)]
+    pub install_state: InstallState,
+    #[serde(default)]
+    pub developer: String,     // Connu hors-ligne pour Steam (appinfo.vdf), vide sinon
+    #[serde(default)]
+    pub publisher: String,
+    #[serde(default)]
+    pub release_date: u64,     // Timestamp Unix (secondes), 0 si inconnu
+}
+
+// État d'installation tel que rapporté par le launcher.
+// Côté frontend : { "state": "downloading", "bytes_downloaded": 123, "bytes_to_download": 456 }
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq)]
+#[serde(tag = "state", rename_all = "snake_case")]
+pub enum InstallState {
+    #[default]
+    Installed,
+    UpdateRequired,
+    Downloading { bytes_downloaded: u64, bytes_to_download: u64 },
+    /// Le jeu a été désinstallé mais son manifeste est resté sur le disque.
+    Uninstalled,
+}
+
+// Configuration complète du thème
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct ThemeConfig {
+    pub accent: String,       // Couleur principale (boutons, favoris)
+    pub bg_from: String,      // Dégradé fond haut
+    pub bg_to: String,        // Dégradé fond bas
+    pub card_bg: String,      // Couleur de fond des cartes
+    pub text_primary: String, // Couleur du texte principal
+}
+
+// Valeurs par défaut (Thème sombre standard)
+impl Default for ThemeConfig {
+    fn default() -> Self {
+        Self {
+            accent: "#5865F2".to_string(),     // Discord Blurple
+            bg_from: "#121212".to_string(),    // Dark Grey
+            bg_to: "#0a0a0a".to_string(),      // Black
+            card_bg: "#1e1e1e".to_string(),    // Card Grey
+            text_primary: "#f3f4f6".to_string(), // White/Gray
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Default, Clone)]
+pub struct UserData {
+    pub favorites: HashSet<String>,
+    pub custom_games: Vec<Game>,
+    pub selected_drives: HashSet<String>,
+    
+    // On utilise #[serde(default)] pour que les anciens fichiers de config 
+    // (qui n'ont pas encore 'theme') chargent les valeurs par défaut sans planter.
+    #[serde(default)] 
+    pub theme: ThemeConfig, 
+
+    // Afficher aussi les outils / runtimes / redistribuables (Proton, Steamworks...)
+    #[serde(default)]
+    pub show_tools: bool,
+
+    // Systèmes rétro déclarés par l'utilisateur (un dossier de ROMs + un émulateur chacun)
+    #[serde(default)]
+    pub emulator_systems: Vec<EmulatorSystem>,
+
+    // Dossiers parcourus par le scan profond (vide = les disques sélectionnés)
+    #[serde(default)]
+    pub deep_scan_folders: Vec<String>,
+}
+
+// Un système émulé : chaque ROM du dossier devient un jeu dont la plateforme est `name`
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq)]
+pub struct EmulatorSystem {
+    pub name: String,            // Nom affiché et plateforme des jeux (ex: "SNES")
+    pub rom_folder: String,      // Dossier parcouru (sous-dossiers compris)
+    pub extensions: Vec<String>, // Extensions sans le point (ex: ["sfc", "smc", "zip"])
+    pub launch_template: String, // Commande, ex: retroarch -L snes9x_libretro.so {rom}
+}
+
+#[derive(Serialize, Clone, Default)]
+pub struct HltbTime {
+    pub main: i32,
+    pub main_extra: i32,
+    pub completionist: i32,
+}
+
+#[derive(Serialize, Clone, Default)]
+pub struct ModInfo {
+    pub nexus: Option<String>,
+    pub thunderstore: Option<String>,
+}
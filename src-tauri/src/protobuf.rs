@@ -0,0 +1,125 @@
+// ===================== DÉCODAGE PROTOBUF =====================
+// Décodeur minimal du format filaire protobuf, sans schéma : chaque appelant ne garde que
+// les champs qu'il connaît et saute le reste (product.db de Battle.net, cache de configurations
+// d'Ubisoft Connect...), ce qui tolère les ajouts de champs par les éditeurs.
+
+pub enum ProtoValue<'a> {
+    Varint(u64),
+    Bytes(&'a [u8]),
+    /// Champ de taille fixe (fixed32/fixed64, float/double), sauté
+    Fixed,
+}
+
+pub struct ProtoReader<'a> {
+    data: &'a [u8],
+    pos: usize,
+}
+
+impl<'a> ProtoReader<'a> {
+    pub fn new(data: &'a [u8]) -> Self {
+        Self { data, pos: 0 }
+    }
+
+    /// Itère sur les champs (numéro, valeur) du message ; s'arrête à la première erreur.
+    pub fn fields(mut self) -> impl Iterator<Item = Result<(u64, ProtoValue<'a>), String>> {
+        let mut failed = false;
+        std::iter::from_fn(move || {
+            if failed || self.pos >= self.data.len() { return None; }
+            let field = self.read_field();
+            failed = field.is_err();
+            Some(field)
+        })
+    }
+
+    fn read_field(&mut self) -> Result<(u64, ProtoValue<'a>), String> {
+        let key = self.read_varint()?;
+        let value = match key & 0x7 {
+            0 => ProtoValue::Varint(self.read_varint()?),
+            1 => {
+                self.take(8)?;
+                ProtoValue::Fixed
+            }
+            2 => {
+                let len = usize::try_from(self.read_varint()?).map_err(|_| "protobuf: longueur invalide".to_string())?;
+                ProtoValue::Bytes(self.take(len)?)
+            }
+            5 => {
+                self.take(4)?;
+                ProtoValue::Fixed
+            }
+            other => return Err(format!("protobuf: type filaire {} non géré à l'offset {}", other, self.pos)),
+        };
+        Ok((key >> 3, value))
+    }
+
+    fn read_varint(&mut self) -> Result<u64, String> {
+        let mut value = 0u64;
+        for shift in (0..64).step_by(7) {
+            let Some(&byte) = self.data.get(self.pos) else { break };
+            self.pos += 1;
+            value |= u64::from(byte & 0x7F) << shift;
+            if byte & 0x80 == 0 { return Ok(value); }
+        }
+        Err("protobuf: varint invalide".to_string())
+    }
+
+    fn take(&mut self, len: usize) -> Result<&'a [u8], String> {
+        let end = self.pos.checked_add(len).filter(|&end| end <= self.data.len());
+        let Some(end) = end else { return Err("protobuf: fin de données inattendue".to_string()) };
+        let slice = &self.data[self.pos..end];
+        self.pos = end;
+        Ok(slice)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // (numéro, valeur varint, contenu) ; les champs fixes n'ont ni l'un ni l'autre
+    type Field = (u64, Option<u64>, Option<Vec<u8>>);
+
+    fn collect(data: &[u8]) -> Vec<Result<Field, String>> {
+        ProtoReader::new(data)
+            .fields()
+            .map(|field| {
+                field.map(|(number, value)| match value {
+                    ProtoValue::Varint(v) => (number, Some(v), None),
+                    ProtoValue::Bytes(b) => (number, None, Some(b.to_vec())),
+                    ProtoValue::Fixed => (number, None, None),
+                })
+            })
+            .collect()
+    }
+
+    #[test]
+    fn reads_multi_byte_varints() {
+        // 1: 150 (exemple de la documentation protobuf), 2: u64::MAX sur 10 octets
+        let mut data = vec![0x08, 0x96, 0x01, 0x10];
+        data.extend([0xFF; 9]);
+        data.push(0x01);
+        assert_eq!(collect(&data), vec![Ok((1, Some(150), None)), Ok((2, Some(u64::MAX), None))]);
+    }
+
+    #[test]
+    fn reads_length_delimited_fields_and_skips_fixed_ones() {
+        let data = [0x12, 0x03, b'a', b'b', b'c', 0x19, 1, 2, 3, 4, 5, 6, 7, 8, 0x25, 1, 2, 3, 4, 0x1A, 0x00];
+        assert_eq!(
+            collect(&data),
+            vec![Ok((2, None, Some(b"abc".to_vec()))), Ok((3, None, None)), Ok((4, None, None)), Ok((3, None, Some(Vec::new())))]
+        );
+    }
+
+    #[test]
+    fn stops_at_the_first_error() {
+        // Longueur au-delà des données
+        let fields = collect(&[0x08, 0x01, 0x12, 0x05, b'a', 0x08, 0x02]);
+        assert_eq!(fields.len(), 2);
+        assert!(fields[1].is_err());
+        // Varint coupé, varint trop long, type filaire 3 (groupe) non géré
+        assert!(collect(&[0x08, 0x80])[0].is_err());
+        assert!(collect(&[0x08, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0x01])[0].is_err());
+        assert!(collect(&[0x0B, 0x00])[0].is_err());
+        assert!(collect(&[0x09, 0x00, 0x00])[0].is_err());
+    }
+}
@@ -0,0 +1,172 @@
+use std::path::{Path, PathBuf};
+use rusqlite::Connection;
+use serde_json::Value;
+
+use super::{home_dir, open_sqlite_read_only, scavenge_image, GameProvider};
+use crate::models::Game;
+
+// ===================== AMAZON GAMES =====================
+// L'app Amazon Games enregistre ses installations dans Data\Games\Sql\GameInstallInfo.sqlite
+// (table DbSet : Id, ProductTitle, InstallDirectory, Installed).
+// Chaque jeu a un fuel.json à la racine de son dossier qui décrit l'exécutable principal.
+
+/// Ligne de la table `DbSet` de GameInstallInfo.sqlite.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct AmazonInstall {
+    /// ex: "amzn1.adg.product.xxxx"
+    pub id: String,
+    pub title: String,
+    pub install_dir: String,
+}
+
+pub struct AmazonProvider {
+    install_db: PathBuf,
+}
+
+impl AmazonProvider {
+    pub fn new() -> Self {
+        let local_app_data = std::env::var_os("LOCALAPPDATA")
+            .filter(|v| !v.is_empty())
+            .map(PathBuf::from)
+            .or_else(|| home_dir().map(|h| h.join("AppData").join("Local")))
+            .unwrap_or_default();
+        Self::with_install_db(local_app_data.join("Amazon Games").join("Data").join("Games").join("Sql").join("GameInstallInfo.sqlite"))
+    }
+
+    /// Provider lisant une base GameInstallInfo.sqlite précise (copie d'un PC Windows, fixture...).
+    pub fn with_install_db(install_db: PathBuf) -> Self {
+        Self { install_db }
+    }
+}
+
+impl Default for AmazonProvider {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl GameProvider for AmazonProvider {
+    fn platform_id(&self) -> &str {
+        "Amazon"
+    }
+
+    fn scan(&self) -> Vec<Game> {
+        let Some(conn) = open_sqlite_read_only(&self.install_db) else { return Vec::new() };
+        get_amazon_games(&conn)
+    }
+
+    fn launch(&self, id: &str, _exe_path: &str) {
+        opener::open(format!("amazon-games://play/{}", id)).ok();
+    }
+
+    fn open_store_page(&self, _id: &str) {
+        opener::open("amazon-games://").ok();
+    }
+}
+
+pub fn get_amazon_games(conn: &Connection) -> Vec<Game> {
+    read_installs(conn)
+        .into_iter()
+        .map(|install| {
+            let exe_path = read_fuel_command(Path::new(&install.install_dir)).unwrap_or_default();
+            Game {
+                id: install.id,
+                title: install.title,
+                platform: "Amazon".to_string(),
+                image_path: scavenge_image(&install.install_dir),
+                exe_path,
+                install_dir: install.install_dir,
+                ..Default::default()
+            }
+        })
+        .collect()
+}
+
+/// Jeux marqués installés dans GameInstallInfo.sqlite.
+pub fn read_installs(conn: &Connection) -> Vec<AmazonInstall> {
+    let query = "SELECT Id, ProductTitle, InstallDirectory FROM DbSet WHERE Installed = 1";
+    let Ok(mut stmt) = conn.prepare(query) else { return Vec::new() };
+    let rows = stmt.query_map([], |row| {
+        Ok(AmazonInstall {
+            id: row.get::<_, Option<String>>(0)?.unwrap_or_default(),
+            title: row.get::<_, Option<String>>(1)?.unwrap_or_default(),
+            install_dir: row.get::<_, Option<String>>(2)?.unwrap_or_default(),
+        })
+    });
+    match rows {
+        Ok(rows) => rows.flatten().filter(|i| !i.id.is_empty() && !i.title.is_empty()).collect(),
+        Err(_) => Vec::new(),
+    }
+}
+
+/// Exécutable principal déclaré dans <dossier du jeu>/fuel.json : { "Main": { "Command": "bin/Game.exe" } }
+pub fn read_fuel_command(install_dir: &Path) -> Option<String> {
+    let content = std::fs::read_to_string(install_dir.join("fuel.json")).ok()?;
+    let json: Value = serde_json::from_str(&content).ok()?;
+    let command = json["Main"]["Command"].as_str().filter(|c| !c.is_empty())?;
+    Some(install_dir.join(command).to_string_lossy().to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+
+    // Table DbSet réduite aux colonnes lues, plus une colonne ignorée
+    fn install_db(install_dir: &Path) -> Connection {
+        let conn = Connection::open_in_memory().unwrap();
+        conn.execute_batch(
+            "CREATE TABLE DbSet (Id TEXT, ProductTitle TEXT, InstallDirectory TEXT, Installed INTEGER, ProductAsin TEXT);",
+        )
+        .unwrap();
+        let rows: [(Option<&str>, Option<&str>, String, i64); 4] = [
+            (Some("amzn1.adg.product.1"), Some("Fallout 3"), install_dir.to_string_lossy().to_string(), 1),
+            (Some("amzn1.adg.product.2"), Some("Désinstallé"), "C:\\Amazon Games\\Library\\Old".to_string(), 0),
+            (Some("amzn1.adg.product.3"), None, "C:\\Amazon Games\\Library\\SansTitre".to_string(), 1),
+            (None, Some("Sans id"), "".to_string(), 1),
+        ];
+        for (id, title, dir, installed) in rows {
+            conn.execute(
+                "INSERT INTO DbSet (Id, ProductTitle, InstallDirectory, Installed, ProductAsin) VALUES (?1, ?2, ?3, ?4, 'B0')",
+                rusqlite::params![id, title, dir, installed],
+            )
+            .unwrap();
+        }
+        conn
+    }
+
+    #[test]
+    fn reads_installed_rows_only() {
+        let conn = install_db(Path::new("C:\\Amazon Games\\Library\\Fallout 3"));
+        assert_eq!(
+            read_installs(&conn),
+            vec![AmazonInstall {
+                id: "amzn1.adg.product.1".to_string(),
+                title: "Fallout 3".to_string(),
+                install_dir: "C:\\Amazon Games\\Library\\Fallout 3".to_string(),
+            }]
+        );
+        // Base sans table DbSet : aucune installation
+        assert!(read_installs(&Connection::open_in_memory().unwrap()).is_empty());
+    }
+
+    #[test]
+    fn reads_the_fuel_command() {
+        let dir = std::env::temp_dir().join(format!("geewers-amazon-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        fs::write(dir.join("fuel.json"), r#"{ "SchemaVersion": "2", "Main": { "Command": "bin/Fallout3.exe", "Args": [] } }"#).unwrap();
+        assert_eq!(read_fuel_command(&dir), Some(dir.join("bin/Fallout3.exe").to_string_lossy().to_string()));
+
+        let games = get_amazon_games(&install_db(&dir));
+        assert_eq!(games.len(), 1);
+        assert_eq!(games[0].platform, "Amazon");
+        assert_eq!(games[0].exe_path, dir.join("bin/Fallout3.exe").to_string_lossy());
+
+        fs::write(dir.join("fuel.json"), r#"{ "Main": { "Command": "" } }"#).unwrap();
+        assert_eq!(read_fuel_command(&dir), None);
+        fs::write(dir.join("fuel.json"), "{ pas du json").unwrap();
+        assert_eq!(read_fuel_command(&dir), None);
+        fs::remove_dir_all(&dir).ok();
+        assert_eq!(read_fuel_command(&dir), None);
+    }
+}
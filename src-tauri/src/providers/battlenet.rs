@@ -0,0 +1,341 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+use super::{scavenge_image, GameProvider};
+use crate::models::{Game, InstallState};
+use crate::protobuf::{ProtoReader, ProtoValue};
+use crate::registry::{Hive, RegistryReader};
+
+// ===================== BATTLE.NET =====================
+// L'agent Battle.net tient la liste des produits installés dans ProgramData\Battle.net\Agent\product.db,
+// un message protobuf "Database" :
+//   Database          { 1: repeated ProductInstall }
+//   ProductInstall    { 1: uid, 2: product_code, 3: UserSettings, 4: CachedProductState }
+//   UserSettings      { 1: install_path, 2: play_region, 10: version_branch }
+//   CachedProductState{ 1: BaseProductState, 4: UpdateProgress }
+//   BaseProductState  { 1: installed, 2: playable, 7: current_version_str }
+//   UpdateProgress    { 2: progress (double), 4: total_to_download, 5: download_remaining }
+// Les titres affichés viennent d'une table connue, sinon des clés Uninstall du registre.
+
+/// Un produit de product.db.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct BattleNetInstall {
+    /// ex: "diablo3_enus"
+    pub uid: String,
+    /// ex: "d3", "pro", "wow"
+    pub product_code: String,
+    pub install_path: String,
+    pub version: String,
+    pub installed: bool,
+    pub playable: bool,
+    pub total_to_download: u64,
+    pub download_remaining: u64,
+}
+
+// Produits de l'agent lui-même, à ne pas afficher
+const IGNORED_PRODUCTS: [&str; 3] = ["agent", "bna", "battle.net"];
+
+// product_code -> (titre, code de lancement attendu par battlenet://)
+const KNOWN_PRODUCTS: [(&str, &str, &str); 21] = [
+    ("anbs", "Diablo Immortal", "ANBS"),
+    ("auks", "Call of Duty", "AUKS"),
+    ("d3", "Diablo III", "D3"),
+    ("fenris", "Diablo IV", "Fen"),
+    ("fore", "Call of Duty: Vanguard", "FORE"),
+    ("gryphon", "Warcraft Rumble", "GRY"),
+    ("hero", "Heroes of the Storm", "Hero"),
+    ("hs_beta", "Hearthstone", "WTCG"),
+    ("lazarus", "Call of Duty: Modern Warfare 2 Campaign Remastered", "LAZR"),
+    ("odin", "Call of Duty: Modern Warfare", "ODIN"),
+    ("osi", "Diablo II: Resurrected", "OSI"),
+    ("pro", "Overwatch 2", "Pro"),
+    ("rtro", "Blizzard Arcade Collection", "RTRO"),
+    ("s1", "StarCraft: Remastered", "S1"),
+    ("s2", "StarCraft II", "S2"),
+    ("viper", "Call of Duty: Black Ops 4", "VIPR"),
+    ("w3", "Warcraft III: Reforged", "W3"),
+    ("wlby", "Crash Bandicoot 4: It's About Time", "WLBY"),
+    ("wow", "World of Warcraft", "WoW"),
+    ("wow_classic", "World of Warcraft Classic", "WoWC"),
+    ("zeus", "Call of Duty: Black Ops Cold War", "ZEUS"),
+];
+
+pub struct BattleNetProvider {
+    registry: Arc<dyn RegistryReader>,
+    product_db: PathBuf,
+}
+
+impl BattleNetProvider {
+    pub fn new(registry: Arc<dyn RegistryReader>) -> Self {
+        let program_data = std::env::var_os("ProgramData")
+            .filter(|v| !v.is_empty())
+            .map(PathBuf::from)
+            .unwrap_or_else(|| PathBuf::from("C:\\ProgramData"));
+        Self::with_product_db(registry, program_data.join("Battle.net").join("Agent").join("product.db"))
+    }
+
+    /// Provider lisant un product.db précis (copie d'un PC Windows, fixture...).
+    pub fn with_product_db(registry: Arc<dyn RegistryReader>, product_db: PathBuf) -> Self {
+        Self { registry, product_db }
+    }
+}
+
+impl GameProvider for BattleNetProvider {
+    fn platform_id(&self) -> &str {
+        "Battle.net"
+    }
+
+    fn scan(&self) -> Vec<Game> {
+        let Ok(data) = fs::read(&self.product_db) else { return Vec::new() };
+        let installs = parse_product_db(&data).unwrap_or_default();
+        get_battlenet_games(&installs, self.registry.as_ref())
+    }
+
+    // L'id d'un jeu est déjà le code de lancement
+    fn launch(&self, id: &str, _exe_path: &str) {
+        opener::open(format!("battlenet://{}", id)).ok();
+    }
+
+    fn open_store_page(&self, id: &str) {
+        opener::open(format!("battlenet://{}", id)).ok();
+    }
+}
+
+pub fn get_battlenet_games(installs: &[BattleNetInstall], registry: &dyn RegistryReader) -> Vec<Game> {
+    let uninstall_titles = blizzard_uninstall_titles(registry);
+    let mut games: Vec<Game> = Vec::new();
+
+    for install in installs {
+        let code = install.product_code.to_lowercase();
+        if IGNORED_PRODUCTS.contains(&code.as_str()) || !install.installed || install.install_path.is_empty() { continue; }
+
+        let known = KNOWN_PRODUCTS.iter().find(|(c, _, _)| *c == code);
+        let launch_code = known.map(|(_, _, l)| l.to_string()).unwrap_or_else(|| install.product_code.clone());
+        if games.iter().any(|g| g.id == launch_code) { continue; }
+
+        let title = known
+            .map(|(_, t, _)| t.to_string())
+            .or_else(|| {
+                uninstall_titles
+                    .iter()
+                    .find(|(location, _)| same_path(location, &install.install_path))
+                    .map(|(_, name)| name.clone())
+            })
+            .unwrap_or_else(|| {
+                Path::new(&install.install_path).file_name().unwrap_or_default().to_string_lossy().to_string()
+            });
+
+        games.push(Game {
+            id: launch_code,
+            title,
+            platform: "Battle.net".to_string(),
+            image_path: scavenge_image(&install.install_path),
+            install_dir: install.install_path.clone(),
+            install_state: install.install_state(),
+            ..Default::default()
+        });
+    }
+    games
+}
+
+impl BattleNetInstall {
+    pub fn install_state(&self) -> InstallState {
+        if self.download_remaining > 0 && self.total_to_download >= self.download_remaining {
+            InstallState::Downloading {
+                bytes_downloaded: self.total_to_download - self.download_remaining,
+                bytes_to_download: self.total_to_download,
+            }
+        } else if !self.playable {
+            InstallState::UpdateRequired
+        } else {
+            InstallState::Installed
+        }
+    }
+}
+
+// (InstallLocation, DisplayName) des entrées Uninstall publiées par Blizzard / Activision
+fn blizzard_uninstall_titles(registry: &dyn RegistryReader) -> Vec<(String, String)> {
+    let uninstall_paths = [
+        "SOFTWARE\\Microsoft\\Windows\\CurrentVersion\\Uninstall",
+        "SOFTWARE\\WOW6432Node\\Microsoft\\Windows\\CurrentVersion\\Uninstall",
+    ];
+    let mut titles = Vec::new();
+    for path in uninstall_paths {
+        for subkey_name in registry.subkeys(Hive::LocalMachine, path) {
+            let subkey = format!("{}\\{}", path, subkey_name);
+            let value = |name: &str| registry.value(Hive::LocalMachine, &subkey, name).unwrap_or_default();
+            let publisher = value("Publisher");
+            if publisher.contains("Blizzard") || publisher.contains("Activision") {
+                let title = value("DisplayName");
+                let location = value("InstallLocation");
+                if !title.is_empty() && !location.is_empty() {
+                    titles.push((location, title));
+                }
+            }
+        }
+    }
+    titles
+}
+
+// product.db utilise des "/" alors que le registre utilise des "\"
+fn same_path(a: &str, b: &str) -> bool {
+    let normalize = |p: &str| p.replace('\\', "/").trim_end_matches('/').to_lowercase();
+    normalize(a) == normalize(b)
+}
+
+// ===================== DÉCODAGE DE product.db =====================
+// On ne garde que les champs listés plus haut, tout le reste est sauté.
+
+/// Décode le contenu de product.db.
+pub fn parse_product_db(data: &[u8]) -> Result<Vec<BattleNetInstall>, String> {
+    let mut installs = Vec::new();
+    for field in ProtoReader::new(data).fields() {
+        if let (1, ProtoValue::Bytes(bytes)) = field? {
+            installs.push(parse_product_install(bytes)?);
+        }
+    }
+    Ok(installs)
+}
+
+fn parse_product_install(data: &[u8]) -> Result<BattleNetInstall, String> {
+    let mut install = BattleNetInstall::default();
+    for field in ProtoReader::new(data).fields() {
+        match field? {
+            (1, ProtoValue::Bytes(b)) => install.uid = text(b),
+            (2, ProtoValue::Bytes(b)) => install.product_code = text(b),
+            (3, ProtoValue::Bytes(settings)) => {
+                for field in ProtoReader::new(settings).fields() {
+                    if let (1, ProtoValue::Bytes(b)) = field? {
+                        install.install_path = text(b);
+                    }
+                }
+            }
+            (4, ProtoValue::Bytes(state)) => parse_cached_state(state, &mut install)?,
+            _ => {}
+        }
+    }
+    Ok(install)
+}
+
+fn parse_cached_state(data: &[u8], install: &mut BattleNetInstall) -> Result<(), String> {
+    for field in ProtoReader::new(data).fields() {
+        match field? {
+            (1, ProtoValue::Bytes(base)) => {
+                for field in ProtoReader::new(base).fields() {
+                    match field? {
+                        (1, ProtoValue::Varint(v)) => install.installed = v != 0,
+                        (2, ProtoValue::Varint(v)) => install.playable = v != 0,
+                        (7, ProtoValue::Bytes(b)) => install.version = text(b),
+                        _ => {}
+                    }
+                }
+            }
+            (4, ProtoValue::Bytes(progress)) => {
+                for field in ProtoReader::new(progress).fields() {
+                    match field? {
+                        (4, ProtoValue::Varint(v)) => install.total_to_download = v,
+                        (5, ProtoValue::Varint(v)) => install.download_remaining = v,
+                        _ => {}
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+    Ok(())
+}
+
+fn text(bytes: &[u8]) -> String {
+    String::from_utf8_lossy(bytes).into_owned()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Encodage protobuf à la main : clé (numéro << 3 | type filaire) puis valeur
+    fn varint(out: &mut Vec<u8>, mut value: u64) {
+        while value >= 0x80 {
+            out.push((value as u8) | 0x80);
+            value >>= 7;
+        }
+        out.push(value as u8);
+    }
+
+    fn bytes(out: &mut Vec<u8>, number: u64, value: &[u8]) {
+        varint(out, number << 3 | 2);
+        varint(out, value.len() as u64);
+        out.extend(value);
+    }
+
+    fn number(out: &mut Vec<u8>, number: u64, value: u64) {
+        varint(out, number << 3);
+        varint(out, value);
+    }
+
+    fn product_install(uid: &str, code: &str, path: &str, remaining: u64) -> Vec<u8> {
+        let mut settings = Vec::new();
+        bytes(&mut settings, 1, path.as_bytes());
+        bytes(&mut settings, 2, b"EU");
+        let mut base = Vec::new();
+        number(&mut base, 1, 1);
+        number(&mut base, 2, 1);
+        bytes(&mut base, 7, b"2.7.6.12345");
+        let mut progress = Vec::new();
+        // progress (double) : champ fixe sauté
+        progress.push(2 << 3 | 1);
+        progress.extend(0.5f64.to_le_bytes());
+        number(&mut progress, 4, 4_000_000_000);
+        number(&mut progress, 5, remaining);
+        let mut state = Vec::new();
+        bytes(&mut state, 1, &base);
+        bytes(&mut state, 4, &progress);
+
+        let mut install = Vec::new();
+        bytes(&mut install, 1, uid.as_bytes());
+        bytes(&mut install, 2, code.as_bytes());
+        bytes(&mut install, 3, &settings);
+        bytes(&mut install, 4, &state);
+        // Champ inconnu ajouté par une version future de l'agent
+        number(&mut install, 9, 42);
+        install
+    }
+
+    #[test]
+    fn decodes_one_install() {
+        let mut db = Vec::new();
+        bytes(&mut db, 1, &product_install("diablo3_enus", "d3", "C:/Program Files (x86)/Diablo III", 1_000_000_000));
+
+        let installs = parse_product_db(&db).unwrap();
+        assert_eq!(
+            installs,
+            vec![BattleNetInstall {
+                uid: "diablo3_enus".to_string(),
+                product_code: "d3".to_string(),
+                install_path: "C:/Program Files (x86)/Diablo III".to_string(),
+                version: "2.7.6.12345".to_string(),
+                installed: true,
+                playable: true,
+                total_to_download: 4_000_000_000,
+                download_remaining: 1_000_000_000,
+            }]
+        );
+        assert_eq!(
+            installs[0].install_state(),
+            InstallState::Downloading { bytes_downloaded: 3_000_000_000, bytes_to_download: 4_000_000_000 }
+        );
+    }
+
+    #[test]
+    fn rejects_truncated_or_invalid_databases() {
+        let mut db = Vec::new();
+        bytes(&mut db, 1, &product_install("pro", "pro", "C:/Overwatch", 0));
+        for len in 1..db.len() {
+            assert!(parse_product_db(&db[..len]).is_err(), "longueur {}", len);
+        }
+        assert!(parse_product_db(b"\x0A\xFF\xFF\xFF\xFF\xFF\xFF\xFF\xFF\xFF\x01").is_err());
+        assert!(parse_product_db(b"\x0F\x00").is_err());
+        assert_eq!(parse_product_db(&[]).unwrap(), Vec::new());
+    }
+}
@@ -0,0 +1,41 @@
+use std::process::Command;
+
+use super::{split_command_line, GameProvider};
+use crate::models::{Game, UserData};
+
+/// Jeux ajoutés à la main par l'utilisateur.
+/// Ils sont stockés dans `UserData.custom_games`, le scan ne renvoie donc rien.
+pub struct CustomProvider;
+
+impl GameProvider for CustomProvider {
+    fn platform_id(&self) -> &str {
+        "Custom"
+    }
+
+    fn scan(&self) -> Vec<Game> {
+        Vec::new()
+    }
+
+    // Un jeu importé d'un raccourci garde ses arguments et son dossier de travail :
+    // on lance alors l'exécutable nous-mêmes, sinon on laisse le système l'ouvrir.
+    fn launch_with_settings(&self, id: &str, exe_path: &str, settings: &UserData) {
+        if let Some(game) = settings.custom_games.iter().find(|g| g.id == id) {
+            if !game.launch_options.is_empty() || !game.install_dir.is_empty() {
+                let mut command = Command::new(&game.exe_path);
+                command.args(split_command_line(&game.launch_options));
+                if !game.install_dir.is_empty() {
+                    command.current_dir(&game.install_dir);
+                }
+                if command.spawn().is_ok() {
+                    return;
+                }
+            }
+        }
+        self.launch(id, exe_path);
+    }
+
+    // Pour les jeux Custom, le frontend envoie directement le dossier (ou une URL) à ouvrir
+    fn open_store_page(&self, id: &str) {
+        opener::open(id).ok();
+    }
+}
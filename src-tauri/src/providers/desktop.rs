@@ -0,0 +1,415 @@
+use std::collections::HashSet;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+use super::{home_dir, split_command_line, GameProvider};
+use crate::models::Game;
+
+// ===================== ENTRÉES .desktop (LINUX, FLATPAK, SNAP) =====================
+// Les jeux installés hors launcher (paquets, installeurs natifs, Flatpak...) ne sont visibles
+// que par leur fichier .desktop dans les dossiers "applications" de XDG.
+// On garde les entrées de catégorie Game, on résout l'icône dans le thème d'icônes,
+// et l'id d'un jeu est l'id du fichier (ex: "net.supertuxkart.SuperTuxKart.desktop").
+// exe_path contient le chemin du .desktop : l'Exec est relu et interprété au lancement.
+
+/// Les champs utiles du groupe [Desktop Entry].
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct DesktopEntry {
+    pub name: String,
+    pub exec: String,
+    pub icon: String,
+    /// Dossier de travail (clé Path)
+    pub working_dir: String,
+    pub categories: Vec<String>,
+    pub entry_type: String,
+    pub no_display: bool,
+    pub hidden: bool,
+}
+
+// Launchers déjà couverts par leur propre provider, et leurs raccourcis de jeux
+const LAUNCHER_IDS: [&str; 10] = [
+    "steam.desktop",
+    "com.valvesoftware.Steam.desktop",
+    "net.lutris.Lutris.desktop",
+    "lutris.desktop",
+    "com.heroicgameslauncher.hgl.desktop",
+    "heroic.desktop",
+    "io.itch.itch.desktop",
+    "itch.desktop",
+    "org.libretro.RetroArch.desktop",
+    "retroarch.desktop",
+];
+const LAUNCHER_URLS: [&str; 4] = ["steam://", "heroic://", "lutris:", "itch://"];
+
+// Tailles essayées dans le thème d'icônes, de la plus grande à la plus petite
+const ICON_SIZES: [&str; 8] = ["512x512", "256x256", "scalable", "192x192", "128x128", "96x96", "64x64", "48x48"];
+const ICON_EXTENSIONS: [&str; 3] = ["png", "svg", "xpm"];
+
+pub struct DesktopEntryProvider;
+
+impl GameProvider for DesktopEntryProvider {
+    fn platform_id(&self) -> &str {
+        "Linux"
+    }
+
+    fn scan(&self) -> Vec<Game> {
+        let icon_dirs = icon_base_dirs();
+        desktop_entry_files()
+            .into_iter()
+            .filter_map(|(id, path)| {
+                let entry = parse_desktop_entry(&fs::read_to_string(&path).ok()?)?;
+                if !is_listed_game(&id, &entry) { return None; }
+                Some(Game {
+                    id,
+                    title: entry.name.clone(),
+                    platform: "Linux".to_string(),
+                    image_path: resolve_icon(&entry.icon, &icon_dirs).unwrap_or_default(),
+                    exe_path: path.to_string_lossy().to_string(),
+                    install_dir: entry.working_dir,
+                    ..Default::default()
+                })
+            })
+            .collect()
+    }
+
+    fn launch(&self, _id: &str, exe_path: &str) {
+        let Some(entry) = fs::read_to_string(exe_path).ok().and_then(|c| parse_desktop_entry(&c)) else { return };
+        let args = expand_exec(&entry, Path::new(exe_path));
+        let Some((program, args)) = args.split_first() else { return };
+        let mut command = Command::new(program);
+        command.args(args);
+        if !entry.working_dir.is_empty() {
+            command.current_dir(&entry.working_dir);
+        }
+        command.spawn().ok();
+    }
+
+    // Pas de page de boutique pour une entrée .desktop
+    fn open_store_page(&self, _id: &str) {}
+}
+
+/// Dossiers "applications" de XDG, du plus prioritaire au moins prioritaire.
+pub fn application_dirs() -> Vec<PathBuf> {
+    let env_path = |name: &str| std::env::var_os(name).filter(|v| !v.is_empty()).map(PathBuf::from);
+    let mut data_dirs = Vec::new();
+
+    match env_path("XDG_DATA_HOME") {
+        Some(dir) => data_dirs.push(dir),
+        None => data_dirs.extend(home_dir().map(|h| h.join(".local/share"))),
+    }
+    if let Some(home) = home_dir() {
+        data_dirs.push(home.join(".local/share/flatpak/exports/share"));
+    }
+    data_dirs.push(PathBuf::from("/var/lib/flatpak/exports/share"));
+    let system_dirs = std::env::var("XDG_DATA_DIRS").ok().filter(|v| !v.is_empty()).unwrap_or_else(|| "/usr/local/share:/usr/share".to_string());
+    data_dirs.extend(system_dirs.split(':').filter(|d| !d.is_empty()).map(PathBuf::from));
+
+    let mut dirs: Vec<PathBuf> = Vec::new();
+    for dir in data_dirs.into_iter().map(|d| d.join("applications")).chain([PathBuf::from("/var/lib/snapd/desktop/applications")]) {
+        if dir.is_dir() && !dirs.contains(&dir) {
+            dirs.push(dir);
+        }
+    }
+    dirs
+}
+
+/// Fichiers .desktop avec leur id. Si un même id existe dans plusieurs dossiers, le plus prioritaire gagne.
+pub fn desktop_entry_files() -> Vec<(String, PathBuf)> {
+    let mut seen: HashSet<String> = HashSet::new();
+    let mut files = Vec::new();
+    for dir in application_dirs() {
+        let mut found = Vec::new();
+        collect_desktop_files(&dir, "", &mut found);
+        for (id, path) in found {
+            if seen.insert(id.clone()) {
+                files.push((id, path));
+            }
+        }
+    }
+    files
+}
+
+// Les sous-dossiers font partie de l'id : applications/games/foo.desktop -> "games-foo.desktop"
+fn collect_desktop_files(dir: &Path, prefix: &str, out: &mut Vec<(String, PathBuf)>) {
+    let Ok(entries) = fs::read_dir(dir) else { return };
+    for entry in entries.flatten() {
+        let path = entry.path();
+        let name = entry.file_name().to_string_lossy().to_string();
+        if path.is_dir() {
+            collect_desktop_files(&path, &format!("{}{}-", prefix, name), out);
+        } else if name.ends_with(".desktop") {
+            out.push((format!("{}{}", prefix, name), path));
+        }
+    }
+}
+
+/// Parse le groupe [Desktop Entry] d'un fichier .desktop.
+pub fn parse_desktop_entry(content: &str) -> Option<DesktopEntry> {
+    let mut entry = DesktopEntry::default();
+    let mut in_main_group = false;
+    let mut found = false;
+
+    for line in content.lines() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') { continue; }
+        if line.starts_with('[') {
+            in_main_group = line == "[Desktop Entry]";
+            found |= in_main_group;
+            continue;
+        }
+        if !in_main_group { continue; }
+        let Some((key, value)) = line.split_once('=') else { continue };
+        // Les clés localisées (Name[fr]...) sont ignorées : on garde le nom de référence
+        let value = unescape_value(value.trim());
+        match key.trim() {
+            "Name" => entry.name = value,
+            "Exec" => entry.exec = value,
+            "Icon" => entry.icon = value,
+            "Path" => entry.working_dir = value,
+            "Type" => entry.entry_type = value,
+            "Categories" => entry.categories = value.split(';').map(|c| c.trim().to_string()).filter(|c| !c.is_empty()).collect(),
+            "NoDisplay" => entry.no_display = value.eq_ignore_ascii_case("true"),
+            "Hidden" => entry.hidden = value.eq_ignore_ascii_case("true"),
+            _ => {}
+        }
+    }
+    if found { Some(entry) } else { None }
+}
+
+// Échappements des valeurs texte : \s \n \t \r \\
+fn unescape_value(value: &str) -> String {
+    let mut out = String::new();
+    let mut chars = value.chars();
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            out.push(c);
+            continue;
+        }
+        match chars.next() {
+            Some('s') => out.push(' '),
+            Some('n') => out.push('\n'),
+            Some('t') => out.push('\t'),
+            Some('r') => out.push('\r'),
+            Some('\\') => out.push('\\'),
+            Some(other) => {
+                out.push('\\');
+                out.push(other);
+            }
+            None => out.push('\\'),
+        }
+    }
+    out
+}
+
+/// Une entrée visible, de catégorie Game, qui n'est ni un émulateur ni un launcher déjà géré ailleurs.
+pub fn is_listed_game(id: &str, entry: &DesktopEntry) -> bool {
+    let has_category = |name: &str| entry.categories.iter().any(|c| c == name);
+    (entry.entry_type.is_empty() || entry.entry_type == "Application")
+        && !entry.no_display
+        && !entry.hidden
+        && !entry.name.is_empty()
+        && !entry.exec.is_empty()
+        && has_category("Game")
+        && !has_category("Emulator")
+        && !has_category("PackageManager")
+        && !LAUNCHER_IDS.contains(&id)
+        && !LAUNCHER_URLS.iter().any(|url| entry.exec.contains(url))
+}
+
+/// Ligne Exec découpée en arguments, codes de champ interprétés. On lance sans fichier :
+/// %f %F %u %U disparaissent, %i devient --icon <Icon>, %c le nom, %k le chemin du .desktop, %% un %.
+pub fn expand_exec(entry: &DesktopEntry, desktop_file: &Path) -> Vec<String> {
+    let mut args = Vec::new();
+    for arg in split_command_line(&entry.exec) {
+        match arg.as_str() {
+            "%f" | "%F" | "%u" | "%U" | "%d" | "%D" | "%n" | "%N" | "%v" | "%m" => {}
+            "%i" => {
+                if !entry.icon.is_empty() {
+                    args.push("--icon".to_string());
+                    args.push(entry.icon.clone());
+                }
+            }
+            _ => args.push(expand_field_codes(&arg, entry, desktop_file)),
+        }
+    }
+    args
+}
+
+// Codes de champ à l'intérieur d'un argument (ex: --name=%c)
+fn expand_field_codes(arg: &str, entry: &DesktopEntry, desktop_file: &Path) -> String {
+    let mut out = String::new();
+    let mut chars = arg.chars();
+    while let Some(c) = chars.next() {
+        if c != '%' {
+            out.push(c);
+            continue;
+        }
+        match chars.next() {
+            Some('%') => out.push('%'),
+            Some('c') => out.push_str(&entry.name),
+            Some('k') => out.push_str(&desktop_file.to_string_lossy()),
+            Some('i') => out.push_str(&entry.icon),
+            // Codes de fichier/URL ou obsolètes : rien à insérer
+            Some(_) | None => {}
+        }
+    }
+    out
+}
+
+/// Racines des thèmes d'icônes ($XDG_DATA_HOME/icons, ~/.icons, $XDG_DATA_DIRS/icons, exports Flatpak).
+pub fn icon_base_dirs() -> Vec<PathBuf> {
+    let app_dirs = application_dirs();
+    let candidates = home_dir()
+        .map(|h| h.join(".icons"))
+        .into_iter()
+        .chain(app_dirs.iter().filter_map(|d| d.parent().map(|p| p.join("icons"))));
+
+    let mut dirs: Vec<PathBuf> = Vec::new();
+    for dir in candidates {
+        if dir.is_dir() && !dirs.contains(&dir) {
+            dirs.push(dir);
+        }
+    }
+    dirs
+}
+
+/// Chemin de l'icône : absolu tel quel, sinon recherche dans le thème hicolor (la plus grande taille),
+/// puis dans /usr/share/pixmaps.
+pub fn resolve_icon(icon: &str, base_dirs: &[PathBuf]) -> Option<String> {
+    if icon.is_empty() { return None; }
+    let as_path = Path::new(icon);
+    if as_path.is_absolute() {
+        return as_path.is_file().then(|| icon.to_string());
+    }
+
+    let file_names: Vec<String> = ICON_EXTENSIONS.iter().map(|ext| format!("{}.{}", icon, ext)).collect();
+    let in_theme = ICON_SIZES.iter().find_map(|size| {
+        base_dirs.iter().find_map(|base| {
+            file_names
+                .iter()
+                .map(|name| base.join("hicolor").join(size).join("apps").join(name))
+                .find(|p| p.is_file())
+        })
+    });
+    in_theme
+        .or_else(|| file_names.iter().map(|name| Path::new("/usr/share/pixmaps").join(name)).find(|p| p.is_file()))
+        .map(|p| p.to_string_lossy().to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SUPERTUXKART: &str = r#"# Généré par le paquet
+[Desktop Entry]
+Type=Application
+Name=SuperTuxKart
+Name[fr]=SuperTuxKart (français)
+Comment=A kart racing game
+Exec=supertuxkart --name=%c %U
+Icon=supertuxkart
+Path=/opt/supertuxkart
+Categories=Game;ArcadeGame;
+Keywords=tux;game;race;
+
+[Desktop Action Fullscreen]
+Name=Plein écran
+Exec=supertuxkart --fullscreen
+"#;
+
+    #[test]
+    fn parses_the_main_group_only() {
+        let entry = parse_desktop_entry(SUPERTUXKART).unwrap();
+        assert_eq!(entry, DesktopEntry {
+            name: "SuperTuxKart".to_string(),
+            exec: "supertuxkart --name=%c %U".to_string(),
+            icon: "supertuxkart".to_string(),
+            working_dir: "/opt/supertuxkart".to_string(),
+            categories: vec!["Game".to_string(), "ArcadeGame".to_string()],
+            entry_type: "Application".to_string(),
+            no_display: false,
+            hidden: false,
+        });
+        assert_eq!(parse_desktop_entry("[Desktop Action Play]\nName=Jouer\n"), None);
+    }
+
+    #[test]
+    fn filters_listed_games() {
+        let game = parse_desktop_entry(SUPERTUXKART).unwrap();
+        assert!(is_listed_game("supertuxkart.desktop", &game));
+
+        let variant = |f: &dyn Fn(&mut DesktopEntry)| {
+            let mut entry = game.clone();
+            f(&mut entry);
+            is_listed_game("supertuxkart.desktop", &entry)
+        };
+        assert!(!variant(&|e| e.hidden = true));
+        assert!(!variant(&|e| e.no_display = true));
+        assert!(!variant(&|e| e.entry_type = "Link".to_string()));
+        assert!(variant(&|e| e.entry_type = "".to_string()));
+        assert!(!variant(&|e| e.categories = vec!["Utility".to_string()]));
+        // Categories=Games n'est pas Categories=Game
+        assert!(!variant(&|e| e.categories = vec!["Games".to_string()]));
+        assert!(!variant(&|e| e.categories.push("Emulator".to_string())));
+        assert!(!variant(&|e| e.exec = "steam steam://rungameid/620".to_string()));
+        assert!(!is_listed_game("steam.desktop", &game));
+    }
+
+    #[test]
+    fn expands_field_codes() {
+        let desktop_file = Path::new("/usr/share/applications/supertuxkart.desktop");
+        let entry = parse_desktop_entry(SUPERTUXKART).unwrap();
+        // %U disparaît, %c devient le nom
+        assert_eq!(expand_exec(&entry, desktop_file), ["supertuxkart", "--name=SuperTuxKart"]);
+
+        let with_exec = |exec: &str| DesktopEntry { exec: exec.to_string(), ..entry.clone() };
+        assert_eq!(expand_exec(&with_exec("game %f %F %u %U"), desktop_file), ["game"]);
+        assert_eq!(expand_exec(&with_exec("game --progress=100%% %i"), desktop_file), ["game", "--progress=100%", "--icon", "supertuxkart"]);
+        assert_eq!(expand_exec(&with_exec("game --desktop=%k --icon-name=%i"), desktop_file), [
+            "game",
+            "--desktop=/usr/share/applications/supertuxkart.desktop",
+            "--icon-name=supertuxkart",
+        ]);
+        // Sans Icon, %i ne laisse pas de --icon orphelin
+        let no_icon = DesktopEntry { icon: "".to_string(), ..with_exec("game %i") };
+        assert_eq!(expand_exec(&no_icon, desktop_file), ["game"]);
+    }
+
+    #[test]
+    fn unescapes_then_splits_quoted_arguments() {
+        // Premier passage : échappements de la valeur (\\ -> \), second : ceux des guillemets d'Exec
+        let content = "[Desktop Entry]\nName=Script\nExec=sh -c \"echo \\\\\"salut\\\\\" \\\\\\\\ fin\"\nCategories=Game;\n";
+        let entry = parse_desktop_entry(content).unwrap();
+        assert_eq!(entry.exec, r#"sh -c "echo \"salut\" \\ fin""#);
+        assert_eq!(expand_exec(&entry, Path::new("script.desktop")), ["sh", "-c", r#"echo "salut" \ fin"#]);
+
+        assert_eq!(unescape_value(r"a\sb\tc\nd\\e\qf\"), "a b\tc\nd\\e\\qf\\");
+    }
+
+    #[test]
+    fn resolves_hicolor_icons() {
+        let base = std::env::temp_dir().join(format!("geewers-icons-{}", std::process::id()));
+        fs::remove_dir_all(&base).ok();
+        let small = base.join("hicolor/48x48/apps");
+        let large = base.join("hicolor/256x256/apps");
+        fs::create_dir_all(&small).unwrap();
+        fs::create_dir_all(&large).unwrap();
+        fs::write(small.join("geewers-test-game.png"), b"").unwrap();
+        let base_dirs = vec![base.join("absent"), base.clone()];
+        assert_eq!(resolve_icon("geewers-test-game", &base_dirs), Some(small.join("geewers-test-game.png").to_string_lossy().to_string()));
+
+        // La plus grande taille l'emporte
+        fs::write(large.join("geewers-test-game.svg"), b"").unwrap();
+        assert_eq!(resolve_icon("geewers-test-game", &base_dirs), Some(large.join("geewers-test-game.svg").to_string_lossy().to_string()));
+
+        // Chemin absolu : gardé s'il existe
+        let absolute = small.join("geewers-test-game.png").to_string_lossy().to_string();
+        assert_eq!(resolve_icon(&absolute, &[]), Some(absolute.clone()));
+        assert_eq!(resolve_icon(&base.join("absent.png").to_string_lossy(), &base_dirs), None);
+        assert_eq!(resolve_icon("geewers-icone-absente", &base_dirs), None);
+        assert_eq!(resolve_icon("", &base_dirs), None);
+
+        fs::remove_dir_all(&base).ok();
+    }
+}
@@ -0,0 +1,420 @@
+use std::collections::HashSet;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+use super::{scavenge_image, system_drives, GameProvider};
+use crate::models::Game;
+use crate::registry::{Hive, RegistryReader};
+
+// ===================== EA APP (ex-ORIGIN) =====================
+// Chaque jeu installé par l'EA app (ou Origin) contient un manifeste <dossier>\__Installer\installerdata.xml :
+//   <contentIDs><contentID>...</contentID></contentIDs>   -> ids utilisés par origin2://game/launch?offerIds=
+//   <gameTitles><gameTitle locale="en_US">...</gameTitle> (format 4.x) ou <metadata><localeInfo><title> (3.x)
+//   <runtime><launcher><filePath>[HKEY_LOCAL_MACHINE\...\Install Dir]bin\game.exe</filePath><trial>0</trial>
+// L'EA app garde aussi une copie des manifestes dans ProgramData\EA Desktop\InstallData\<jeu>\.
+// Les dossiers de jeux viennent du registre (clés "EA Games", désinstallation) et des dossiers par défaut.
+// L'id d'un jeu est la liste de ses contentIDs séparés par des virgules. Les versions précédentes
+// utilisaient le nom de la clé de désinstallation : `legacy_ids` fait le lien pour les favoris.
+
+const EA_GAMES_KEY: &str = "SOFTWARE\\WOW6432Node\\EA Games";
+const UNINSTALL_KEYS: [&str; 2] = [
+    "SOFTWARE\\Microsoft\\Windows\\CurrentVersion\\Uninstall",
+    "SOFTWARE\\WOW6432Node\\Microsoft\\Windows\\CurrentVersion\\Uninstall",
+];
+
+// Dossiers d'installation par défaut, relatifs à la racine de chaque disque
+const DEFAULT_LIBRARIES: [&str; 3] = ["Program Files\\EA Games", "Program Files (x86)\\Origin Games", "EA Games"];
+
+/// Les informations utiles d'un installerdata.xml.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct EaManifest {
+    pub content_ids: Vec<String>,
+    pub title: String,
+    /// filePath des lanceurs (hors versions d'essai), 64 bits en premier
+    pub launchers: Vec<String>,
+}
+
+pub struct EaProvider {
+    registry: Arc<dyn RegistryReader>,
+    install_data_dir: PathBuf,
+}
+
+impl EaProvider {
+    pub fn new(registry: Arc<dyn RegistryReader>) -> Self {
+        let program_data = std::env::var_os("ProgramData")
+            .filter(|v| !v.is_empty())
+            .map(PathBuf::from)
+            .unwrap_or_else(|| PathBuf::from("C:\\ProgramData"));
+        Self::with_install_data(registry, program_data.join("EA Desktop").join("InstallData"))
+    }
+
+    /// Provider lisant un dossier InstallData précis (copie d'un PC Windows, fixture...).
+    pub fn with_install_data(registry: Arc<dyn RegistryReader>, install_data_dir: PathBuf) -> Self {
+        Self { registry, install_data_dir }
+    }
+
+    // Dossiers pouvant contenir un jeu EA : on ne garde que ceux qui ont un manifeste
+    fn candidate_install_dirs(&self) -> Vec<PathBuf> {
+        let registry = self.registry.as_ref();
+        let mut dirs: Vec<String> = registry
+            .subkeys(Hive::LocalMachine, EA_GAMES_KEY)
+            .into_iter()
+            .filter_map(|name| registry.value(Hive::LocalMachine, &format!("{}\\{}", EA_GAMES_KEY, name), "Install Dir"))
+            .collect();
+        for path in UNINSTALL_KEYS {
+            for name in registry.subkeys(Hive::LocalMachine, path) {
+                dirs.extend(registry.value(Hive::LocalMachine, &format!("{}\\{}", path, name), "InstallLocation"));
+            }
+        }
+        for drive in system_drives() {
+            for library in DEFAULT_LIBRARIES {
+                let Ok(entries) = fs::read_dir(Path::new(&drive).join(library)) else { continue };
+                dirs.extend(entries.flatten().map(|e| e.path().to_string_lossy().to_string()));
+            }
+        }
+
+        dirs.into_iter()
+            .filter(|d| !d.is_empty())
+            .map(|d| PathBuf::from(d.trim_end_matches(['\\', '/'])))
+            .filter(|d| installer_data_path(d).is_file())
+            .collect()
+    }
+}
+
+impl GameProvider for EaProvider {
+    fn platform_id(&self) -> &str {
+        "EA"
+    }
+
+    fn scan(&self) -> Vec<Game> {
+        let mut manifests: Vec<(EaManifest, Option<PathBuf>)> = Vec::new();
+        for dir in self.candidate_install_dirs() {
+            if let Some(manifest) = fs::read_to_string(installer_data_path(&dir)).ok().and_then(|xml| parse_installer_data(&xml)) {
+                manifests.push((manifest, Some(dir)));
+            }
+        }
+        // Copies de l'EA app : le dossier du jeu se déduit du chemin du lanceur (clé de registre)
+        let mut copies = Vec::new();
+        collect_installer_data(&self.install_data_dir, 0, &mut copies);
+        for file in copies {
+            if let Some(manifest) = fs::read_to_string(&file).ok().and_then(|xml| parse_installer_data(&xml)) {
+                manifests.push((manifest, None));
+            }
+        }
+
+        let mut seen_ids: HashSet<String> = HashSet::new();
+        let mut seen_dirs: HashSet<String> = HashSet::new();
+        let mut games = Vec::new();
+        for (manifest, install_dir) in manifests {
+            let Some(game) = ea_game_from_manifest(&manifest, install_dir.as_deref(), self.registry.as_ref()) else { continue };
+            if seen_ids.insert(game.id.clone()) && seen_dirs.insert(game.install_dir.to_lowercase()) {
+                games.push(game);
+            }
+        }
+        games
+    }
+
+    // L'EA app gère la connexion, les mises à jour et le cloud ; sans elle, on lance l'exécutable du manifeste
+    fn launch(&self, id: &str, exe_path: &str) {
+        if opener::open(format!("origin2://game/launch?offerIds={}", id)).is_err() && !exe_path.is_empty() {
+            opener::open(exe_path).ok();
+        }
+    }
+
+    fn open_store_page(&self, _id: &str) {
+        opener::open("origin2://library").ok();
+    }
+
+    // Ancien id : la clé de désinstallation publiée par Electronic Arts pour le même dossier
+    fn legacy_ids(&self, games: &[Game]) -> Vec<(String, String)> {
+        let registry = self.registry.as_ref();
+        let mut ids = Vec::new();
+        for path in UNINSTALL_KEYS {
+            for name in registry.subkeys(Hive::LocalMachine, path) {
+                let subkey = format!("{}\\{}", path, name);
+                let value = |value_name: &str| registry.value(Hive::LocalMachine, &subkey, value_name).unwrap_or_default();
+                let publisher = value("Publisher");
+                if !publisher.contains("Electronic Arts") && publisher != "EA" { continue; }
+                let location = value("InstallLocation");
+                if location.is_empty() { continue; }
+                let game = games.iter().find(|g| g.platform == "EA" && same_path(&g.install_dir, &location));
+                if let Some(game) = game.filter(|g| g.id != name) {
+                    ids.push((name, game.id.clone()));
+                }
+            }
+        }
+        ids
+    }
+}
+
+fn same_path(a: &str, b: &str) -> bool {
+    let normalize = |p: &str| p.replace('\\', "/").trim_end_matches('/').to_lowercase();
+    normalize(a) == normalize(b)
+}
+
+fn installer_data_path(install_dir: &Path) -> PathBuf {
+    install_dir.join("__Installer").join("installerdata.xml")
+}
+
+fn collect_installer_data(dir: &Path, depth: usize, files: &mut Vec<PathBuf>) {
+    let Ok(entries) = fs::read_dir(dir) else { return };
+    for entry in entries.flatten() {
+        let path = entry.path();
+        if path.is_dir() {
+            if depth < 3 {
+                collect_installer_data(&path, depth + 1, files);
+            }
+        } else if path.file_name().is_some_and(|n| n.eq_ignore_ascii_case("installerdata.xml")) {
+            files.push(path);
+        }
+    }
+}
+
+/// Parse un installerdata.xml (formats 3.x et 4.x).
+pub fn parse_installer_data(xml: &str) -> Option<EaManifest> {
+    let doc = roxmltree::Document::parse(xml.trim_start_matches('\u{feff}')).ok()?;
+    let child_text = |node: roxmltree::Node, name: &str| {
+        node.children().find(|c| c.has_tag_name(name)).and_then(|c| c.text()).unwrap_or("").trim().to_string()
+    };
+
+    let content_ids: Vec<String> = doc
+        .descendants()
+        .filter(|n| n.has_tag_name("contentID"))
+        .filter_map(|n| n.text())
+        .map(|t| t.trim().to_string())
+        .filter(|t| !t.is_empty())
+        .collect();
+
+    // Titre anglais de préférence, sinon le premier
+    let titles: Vec<(String, String)> = doc
+        .descendants()
+        .filter_map(|n| {
+            if n.has_tag_name("gameTitle") {
+                Some((n.attribute("locale").unwrap_or("").to_string(), n.text().unwrap_or("").trim().to_string()))
+            } else if n.has_tag_name("localeInfo") {
+                Some((n.attribute("locale").unwrap_or("").to_string(), child_text(n, "title")))
+            } else {
+                None
+            }
+        })
+        .filter(|(_, title)| !title.is_empty())
+        .collect();
+    let title = titles
+        .iter()
+        .find(|(locale, _)| locale.eq_ignore_ascii_case("en_US"))
+        .or_else(|| titles.first())
+        .map(|(_, title)| title.clone())
+        .unwrap_or_default();
+
+    let mut launchers: Vec<(bool, String)> = doc
+        .descendants()
+        .filter(|n| n.has_tag_name("launcher"))
+        .filter(|n| child_text(*n, "trial") != "1")
+        .map(|n| (child_text(n, "requires64BitOS") == "1", child_text(n, "filePath")))
+        .filter(|(_, path)| !path.is_empty())
+        .collect();
+    launchers.sort_by_key(|(is_64, _)| !is_64);
+
+    if content_ids.is_empty() { return None; }
+    Some(EaManifest { content_ids, title, launchers: launchers.into_iter().map(|(_, path)| path).collect() })
+}
+
+/// Chemin d'un lanceur et dossier du jeu. "[HKEY_LOCAL_MACHINE\<clé>\<valeur>]bin\game.exe" est relatif
+/// au dossier lu dans le registre ; un chemin sans crochets est relatif au dossier du manifeste.
+pub fn resolve_launcher(file_path: &str, install_dir: Option<&Path>, registry: &dyn RegistryReader) -> Option<(PathBuf, PathBuf)> {
+    let (base, relative) = match file_path.strip_prefix('[').and_then(|rest| rest.split_once(']')) {
+        Some((reg_path, relative)) => {
+            let from_registry = registry_path_value(reg_path, registry).map(|dir| PathBuf::from(dir.trim_end_matches(['\\', '/'])));
+            (from_registry.or_else(|| install_dir.map(Path::to_path_buf))?, relative)
+        }
+        None => (install_dir?.to_path_buf(), file_path),
+    };
+    let relative = relative.trim_start_matches(['\\', '/']);
+    let exe = if relative.is_empty() { PathBuf::new() } else { base.join(relative) };
+    Some((exe, base))
+}
+
+// "HKEY_LOCAL_MACHINE\SOFTWARE\...\Install Dir" : la dernière partie est le nom de la valeur.
+// Les installeurs 32 bits écrivent "SOFTWARE\..." mais la clé est redirigée sous WOW6432Node.
+fn registry_path_value(reg_path: &str, registry: &dyn RegistryReader) -> Option<String> {
+    let (root, rest) = reg_path.split_once('\\')?;
+    let hive = match root.to_uppercase().as_str() {
+        "HKEY_LOCAL_MACHINE" | "HKLM" => Hive::LocalMachine,
+        "HKEY_CURRENT_USER" | "HKCU" => Hive::CurrentUser,
+        _ => return None,
+    };
+    let (key, name) = rest.rsplit_once('\\')?;
+    let redirected = key
+        .get(..9)
+        .filter(|prefix| prefix.eq_ignore_ascii_case("SOFTWARE\\"))
+        .filter(|_| !key.to_lowercase().contains("wow6432node"))
+        .map(|_| format!("SOFTWARE\\WOW6432Node\\{}", &key[9..]));
+    registry
+        .value(hive, key, name)
+        .or_else(|| registry.value(hive, redirected.as_deref()?, name))
+        .filter(|v| !v.is_empty())
+}
+
+fn ea_game_from_manifest(manifest: &EaManifest, install_dir: Option<&Path>, registry: &dyn RegistryReader) -> Option<Game> {
+    let (exe, resolved_dir) = manifest
+        .launchers
+        .iter()
+        .find_map(|launcher| resolve_launcher(launcher, install_dir, registry))
+        .or_else(|| install_dir.map(|dir| (PathBuf::new(), dir.to_path_buf())))?;
+    // Une copie dans InstallData d'un jeu désinstallé : son dossier n'existe plus
+    if !resolved_dir.is_dir() { return None; }
+
+    let install_dir = resolved_dir.to_string_lossy().to_string();
+    let title = if manifest.title.is_empty() {
+        resolved_dir.file_name()?.to_string_lossy().to_string()
+    } else {
+        manifest.title.clone()
+    };
+    Some(Game {
+        id: manifest.content_ids.join(","),
+        title,
+        platform: "EA".to_string(),
+        image_path: scavenge_image(&install_dir),
+        exe_path: exe.to_string_lossy().to_string(),
+        install_dir,
+        ..Default::default()
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::providers::ProviderRegistry;
+    use crate::registry::MemoryRegistry;
+
+    // installerdata.xml au format 4.x (EA app), avec BOM, essai et lanceurs 32/64 bits
+    const MANIFEST_4X: &str = "\u{feff}<?xml version=\"1.0\" encoding=\"utf-8\"?>
+<DiPManifest version=\"4.0\">
+  <buildMetaData><gameVersion version=\"1.0.58.22810\"/></buildMetaData>
+  <contentIDs>
+    <contentID>1035052</contentID>
+    <contentID>1035053</contentID>
+  </contentIDs>
+  <gameTitles>
+    <gameTitle locale=\"fr_FR\">Battlefield&#8482; 1</gameTitle>
+    <gameTitle locale=\"en_US\"> Battlefield 1 </gameTitle>
+  </gameTitles>
+  <runtime>
+    <launcher uid=\"trial\">
+      <filePath>[HKEY_LOCAL_MACHINE\\SOFTWARE\\EA Games\\Battlefield 1\\Install Dir]bf1_trial.exe</filePath>
+      <trial>1</trial>
+    </launcher>
+    <launcher uid=\"x86\">
+      <filePath>[HKEY_LOCAL_MACHINE\\SOFTWARE\\EA Games\\Battlefield 1\\Install Dir]bf1_x86.exe</filePath>
+      <trial>0</trial>
+    </launcher>
+    <launcher uid=\"x64\">
+      <filePath>[HKEY_LOCAL_MACHINE\\SOFTWARE\\EA Games\\Battlefield 1\\Install Dir]bf1.exe</filePath>
+      <requires64BitOS>1</requires64BitOS>
+      <trial>0</trial>
+    </launcher>
+  </runtime>
+</DiPManifest>";
+
+    // Format 3.x (Origin) : titres sous metadata/localeInfo, chemin relatif au manifeste
+    const MANIFEST_3X: &str = "<DiPManifest version=\"3.0\">
+  <contentIDs><contentID>OFB-EAST:48217</contentID></contentIDs>
+  <metadata>
+    <localeInfo locale=\"de_DE\"><title>Dragon Age: Inquisition (DE)</title></localeInfo>
+    <localeInfo locale=\"en_US\"><title>Dragon Age: Inquisition</title></localeInfo>
+  </metadata>
+  <runtime><launcher><filePath>DragonAgeInquisition.exe</filePath></launcher></runtime>
+</DiPManifest>";
+
+    #[test]
+    fn parses_4x_installer_data() {
+        let manifest = parse_installer_data(MANIFEST_4X).unwrap();
+        assert_eq!(manifest.content_ids, ["1035052", "1035053"]);
+        assert_eq!(manifest.title, "Battlefield 1");
+        // Version d'essai écartée, lanceur 64 bits en premier
+        assert_eq!(manifest.launchers, [
+            "[HKEY_LOCAL_MACHINE\\SOFTWARE\\EA Games\\Battlefield 1\\Install Dir]bf1.exe",
+            "[HKEY_LOCAL_MACHINE\\SOFTWARE\\EA Games\\Battlefield 1\\Install Dir]bf1_x86.exe",
+        ]);
+    }
+
+    #[test]
+    fn parses_3x_installer_data() {
+        let manifest = parse_installer_data(MANIFEST_3X).unwrap();
+        assert_eq!(manifest, EaManifest {
+            content_ids: vec!["OFB-EAST:48217".to_string()],
+            title: "Dragon Age: Inquisition".to_string(),
+            launchers: vec!["DragonAgeInquisition.exe".to_string()],
+        });
+        // Sans titre anglais, le premier titre
+        let german_only = MANIFEST_3X.replace("en_US", "it_IT");
+        assert_eq!(parse_installer_data(&german_only).unwrap().title, "Dragon Age: Inquisition (DE)");
+        // Sans contentID ou XML invalide : pas de manifeste
+        assert_eq!(parse_installer_data(&MANIFEST_3X.replace("OFB-EAST:48217", " ")), None);
+        assert_eq!(parse_installer_data(&MANIFEST_4X[..MANIFEST_4X.len() / 2]), None);
+    }
+
+    #[test]
+    fn resolves_launchers_through_the_registry() {
+        let mut registry = MemoryRegistry::new();
+        // Installeur 32 bits : la valeur est sous WOW6432Node
+        registry.insert(Hive::LocalMachine, "SOFTWARE\\WOW6432Node\\EA Games\\Battlefield 1", "Install Dir", "D:\\Jeux\\Battlefield 1\\");
+        registry.insert(Hive::CurrentUser, "Software\\EA\\Sims 4", "Install Dir", "C:\\Sims 4");
+        registry.insert(Hive::LocalMachine, "SOFTWARE\\EA Games\\Vide", "Install Dir", "");
+
+        let bf1 = "[HKEY_LOCAL_MACHINE\\SOFTWARE\\EA Games\\Battlefield 1\\Install Dir]\\bf1.exe";
+        assert_eq!(
+            resolve_launcher(bf1, None, &registry),
+            Some((PathBuf::from("D:\\Jeux\\Battlefield 1").join("bf1.exe"), PathBuf::from("D:\\Jeux\\Battlefield 1")))
+        );
+        assert_eq!(registry_path_value("HKCU\\Software\\EA\\Sims 4\\Install Dir", &registry), Some("C:\\Sims 4".to_string()));
+        assert_eq!(registry_path_value("HKEY_CLASSES_ROOT\\origin2\\Install Dir", &registry), None);
+        assert_eq!(registry_path_value("HKLM\\SOFTWARE\\EA Games\\Vide\\Install Dir", &registry), None);
+        assert_eq!(registry_path_value("HKLM", &registry), None);
+
+        // Valeur absente du registre : le dossier du manifeste
+        let install_dir = Path::new("/mnt/ea/Vide");
+        assert_eq!(
+            resolve_launcher("[HKEY_LOCAL_MACHINE\\SOFTWARE\\EA Games\\Vide\\Install Dir]game.exe", Some(install_dir), &registry),
+            Some((install_dir.join("game.exe"), install_dir.to_path_buf()))
+        );
+        assert_eq!(resolve_launcher("[HKEY_LOCAL_MACHINE\\SOFTWARE\\EA Games\\Vide\\Install Dir]game.exe", None, &registry), None);
+        // Chemin sans crochets : relatif au dossier du manifeste
+        assert_eq!(
+            resolve_launcher("DragonAgeInquisition.exe", Some(install_dir), &registry),
+            Some((install_dir.join("DragonAgeInquisition.exe"), install_dir.to_path_buf()))
+        );
+        // Lanceur réduit à la clé : le dossier sans exécutable
+        assert_eq!(
+            resolve_launcher("[HKEY_LOCAL_MACHINE\\SOFTWARE\\EA Games\\Battlefield 1\\Install Dir]", None, &registry),
+            Some((PathBuf::new(), PathBuf::from("D:\\Jeux\\Battlefield 1")))
+        );
+    }
+
+    #[test]
+    fn migrates_favorites_saved_with_the_uninstall_key_id() {
+        let mut registry = MemoryRegistry::new();
+        let key = "SOFTWARE\\WOW6432Node\\Microsoft\\Windows\\CurrentVersion\\Uninstall\\{1E3C5D5F-Battlefield 1}";
+        registry.insert(Hive::LocalMachine, key, "Publisher", "Electronic Arts");
+        registry.insert(Hive::LocalMachine, key, "InstallLocation", "C:\\Program Files\\EA Games\\Battlefield 1\\");
+        let other = "SOFTWARE\\Microsoft\\Windows\\CurrentVersion\\Uninstall\\Steam";
+        registry.insert(Hive::LocalMachine, other, "Publisher", "Valve Corporation");
+        registry.insert(Hive::LocalMachine, other, "InstallLocation", "C:\\Program Files\\EA Games\\Battlefield 1");
+
+        let mut providers = ProviderRegistry::new();
+        providers.register(EaProvider::with_install_data(Arc::new(registry), PathBuf::new()));
+        let games = vec![Game {
+            id: "1035052,1035053".to_string(),
+            platform: "EA".to_string(),
+            install_dir: "C:/Program Files/EA Games/Battlefield 1".to_string(),
+            ..Default::default()
+        }];
+
+        let mut favorites: HashSet<String> = ["EA-{1E3C5D5F-Battlefield 1}".to_string(), "Steam-620".to_string()].into();
+        assert!(providers.migrate_favorites(&games, &mut favorites));
+        assert_eq!(favorites, ["EA-1035052,1035053".to_string(), "Steam-620".to_string()].into());
+        // Déjà migré : plus rien à faire
+        assert!(!providers.migrate_favorites(&games, &mut favorites));
+    }
+}
@@ -0,0 +1,271 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+use super::{scavenge_image_in, split_command_line, GameProvider};
+use crate::models::{EmulatorSystem, Game, UserData};
+
+// ===================== ÉMULATION (DOSSIERS DE ROMS) =====================
+// L'utilisateur déclare ses systèmes dans les réglages (UserData.emulator_systems) :
+// un dossier de ROMs, les extensions à garder et la commande de l'émulateur.
+// Chaque ROM devient un jeu dont la plateforme est le nom du système ; l'id est le chemin de la ROM.
+// Placeholders de la commande : {rom} (chemin complet), {name} (nom du fichier sans extension), {dir} (dossier de la ROM).
+
+// Profondeur max de parcours sous le dossier de ROMs (un dossier par jeu multi-disques, par région...)
+const MAX_DEPTH: usize = 4;
+
+// Sous-dossiers où l'on range habituellement les jaquettes
+const ART_FOLDERS: [&str; 6] = ["boxart", "boxarts", "covers", "images", "media", "Named_Boxarts"];
+
+pub struct EmulatorProvider;
+
+impl GameProvider for EmulatorProvider {
+    fn platform_id(&self) -> &str {
+        "Emulator"
+    }
+
+    // Sans les réglages on ne connaît aucun système
+    fn scan(&self) -> Vec<Game> {
+        Vec::new()
+    }
+
+    fn scan_with_settings(&self, settings: &UserData) -> Vec<Game> {
+        settings.emulator_systems.iter().flat_map(get_system_roms).collect()
+    }
+
+    fn launch_with_settings(&self, id: &str, exe_path: &str, settings: &UserData) {
+        let rom = if exe_path.is_empty() { id } else { exe_path };
+        let command = find_system_for_rom(&settings.emulator_systems, Path::new(rom))
+            .map(|system| build_launch_command(&system.launch_template, Path::new(rom)))
+            .unwrap_or_default();
+
+        let Some((program, args)) = command.split_first() else {
+            // Aucune commande déclarée : on laisse le système choisir l'application associée
+            opener::open(rom).ok();
+            return;
+        };
+        Command::new(program).args(args).spawn().ok();
+    }
+
+    // Ouvre le dossier de la ROM
+    fn open_store_page(&self, id: &str) {
+        if let Some(dir) = Path::new(id).parent() {
+            opener::open(dir).ok();
+        }
+    }
+}
+
+/// Une entrée par ROM trouvée dans le dossier du système.
+pub fn get_system_roms(system: &EmulatorSystem) -> Vec<Game> {
+    let extensions = normalized_extensions(system);
+    if system.name.is_empty() || system.rom_folder.is_empty() || extensions.is_empty() { return Vec::new(); }
+
+    let root = Path::new(&system.rom_folder);
+    let mut roms = Vec::new();
+    collect_roms(root, &extensions, 0, &mut roms);
+    roms.sort();
+
+    roms.into_iter()
+        .map(|rom| {
+            let stem = rom.file_stem().unwrap_or_default().to_string_lossy().to_string();
+            let install_dir = rom.parent().map(|p| p.to_string_lossy().to_string()).unwrap_or_default();
+            Game {
+                id: rom.to_string_lossy().to_string(),
+                title: clean_rom_title(&stem),
+                platform: system.name.clone(),
+                image_path: find_rom_art(&rom, root).unwrap_or_default(),
+                exe_path: rom.to_string_lossy().to_string(),
+                install_dir,
+                ..Default::default()
+            }
+        })
+        .collect()
+}
+
+fn normalized_extensions(system: &EmulatorSystem) -> Vec<String> {
+    system
+        .extensions
+        .iter()
+        .map(|e| e.trim().trim_start_matches('.').to_lowercase())
+        .filter(|e| !e.is_empty())
+        .collect()
+}
+
+fn collect_roms(dir: &Path, extensions: &[String], depth: usize, roms: &mut Vec<PathBuf>) {
+    let Ok(entries) = fs::read_dir(dir) else { return };
+    for entry in entries.flatten() {
+        let path = entry.path();
+        let hidden = path.file_name().is_some_and(|n| n.to_string_lossy().starts_with('.'));
+        if hidden { continue; }
+        if path.is_dir() {
+            if depth < MAX_DEPTH {
+                collect_roms(&path, extensions, depth + 1, roms);
+            }
+        } else if has_extension(&path, extensions) {
+            roms.push(path);
+        }
+    }
+}
+
+fn has_extension(path: &Path, extensions: &[String]) -> bool {
+    path.extension()
+        .map(|e| e.to_string_lossy().to_lowercase())
+        .is_some_and(|e| extensions.contains(&e))
+}
+
+/// Retire les tags No-Intro / GoodTools du nom de fichier : "Zelda (USA) [!]" -> "Zelda".
+pub fn clean_rom_title(stem: &str) -> String {
+    let mut title = String::new();
+    let mut depth = 0usize;
+    for c in stem.chars() {
+        match c {
+            '(' | '[' => depth += 1,
+            ')' | ']' => depth = depth.saturating_sub(1),
+            _ if depth == 0 => title.push(c),
+            _ => {}
+        }
+    }
+    let title = title.replace('_', " ").split_whitespace().collect::<Vec<_>>().join(" ");
+    if title.is_empty() { stem.to_string() } else { title }
+}
+
+/// Jaquette portant le nom de la ROM : à côté d'elle, ou dans un sous-dossier boxart/covers/images...
+/// du dossier de la ROM ou de la racine du système.
+pub fn find_rom_art(rom: &Path, system_root: &Path) -> Option<String> {
+    let stem = rom.file_stem()?.to_string_lossy().to_string();
+    // Nom exact de la ROM d'abord, puis sans ses tags ; la casse est ignorée
+    let names = [stem.to_lowercase(), clean_rom_title(&stem).to_lowercase()];
+
+    let mut dirs: Vec<PathBuf> = Vec::new();
+    for base in [rom.parent()?, system_root] {
+        if !dirs.iter().any(|d| d == base) {
+            dirs.push(base.to_path_buf());
+        }
+        dirs.extend(ART_FOLDERS.iter().map(|f| base.join(f)));
+    }
+
+    dirs.iter().find_map(|dir| names.iter().find_map(|name| scavenge_image_in(dir, |s| s == name)))
+}
+
+/// Système déclaré dont le dossier contient la ROM (le plus précis si les dossiers s'imbriquent).
+pub fn find_system_for_rom<'a>(systems: &'a [EmulatorSystem], rom: &Path) -> Option<&'a EmulatorSystem> {
+    systems
+        .iter()
+        .filter(|s| !s.rom_folder.is_empty() && rom.starts_with(&s.rom_folder))
+        .filter(|s| has_extension(rom, &normalized_extensions(s)))
+        .max_by_key(|s| s.rom_folder.len())
+}
+
+/// Programme + arguments à exécuter pour une ROM. Les placeholders sont remplacés après découpage,
+/// un chemin avec des espaces reste donc un seul argument. Sans {rom}, la ROM est ajoutée à la fin.
+pub fn build_launch_command(template: &str, rom: &Path) -> Vec<String> {
+    let rom_path = rom.to_string_lossy().to_string();
+    let name = rom.file_stem().unwrap_or_default().to_string_lossy().to_string();
+    let dir = rom.parent().map(|p| p.to_string_lossy().to_string()).unwrap_or_default();
+
+    let mut args = split_command_line(template);
+    if args.is_empty() { return args; }
+    let has_rom = args.iter().any(|a| a.contains("{rom}"));
+    for arg in args.iter_mut() {
+        *arg = arg.replace("{rom}", &rom_path).replace("{name}", &name).replace("{dir}", &dir);
+    }
+    if !has_rom {
+        args.push(rom_path);
+    }
+    args
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn system(name: &str, rom_folder: &str, extensions: &[&str]) -> EmulatorSystem {
+        EmulatorSystem {
+            name: name.to_string(),
+            rom_folder: rom_folder.to_string(),
+            extensions: extensions.iter().map(|e| e.to_string()).collect(),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn cleans_rom_titles() {
+        assert_eq!(clean_rom_title("Legend of Zelda, The - A Link to the Past (USA) [!]"), "Legend of Zelda, The - A Link to the Past");
+        assert_eq!(clean_rom_title("Super_Mario_World_(Europe)_(Rev 1)"), "Super Mario World");
+        assert_eq!(clean_rom_title("Chrono Trigger [T+Fre1.0 (Terminus)] (Japan)"), "Chrono Trigger");
+        assert_eq!(clean_rom_title("Tetris"), "Tetris");
+        // Nom réduit à des tags : on garde le nom de fichier
+        assert_eq!(clean_rom_title("(Homebrew) [b]"), "(Homebrew) [b]");
+    }
+
+    #[test]
+    fn builds_launch_commands() {
+        let rom = Path::new("/roms/snes/Zelda \"ALttP\" (USA).sfc");
+        // {rom} remplacé après découpage : un seul argument malgré les espaces et guillemets
+        assert_eq!(
+            build_launch_command("retroarch -L \"/usr/lib/libretro/snes9x_libretro.so\" {rom}", rom),
+            ["retroarch", "-L", "/usr/lib/libretro/snes9x_libretro.so", "/roms/snes/Zelda \"ALttP\" (USA).sfc"]
+        );
+        assert_eq!(
+            build_launch_command("mednafen '--save={dir}/{name}.sav'", rom),
+            ["mednafen", "--save=/roms/snes/Zelda \"ALttP\" (USA).sav", "/roms/snes/Zelda \"ALttP\" (USA).sfc"]
+        );
+        // Chemin Windows entre guillemets doubles : les antislashs restent
+        assert_eq!(
+            build_launch_command(r#""C:\Emulateurs\Dolphin\Dolphin.exe" -b -e "{rom}""#, Path::new("D:/Roms/GC/Metroid Prime.iso")),
+            [r"C:\Emulateurs\Dolphin\Dolphin.exe", "-b", "-e", "D:/Roms/GC/Metroid Prime.iso"]
+        );
+        assert!(build_launch_command("", rom).is_empty());
+        assert!(build_launch_command("   ", rom).is_empty());
+    }
+
+    #[test]
+    fn finds_the_most_specific_system() {
+        let systems = [
+            system("Arcade", "/roms", &["zip"]),
+            system("SNES", "/roms/snes", &[".SFC", "smc"]),
+            system("SNES hacks", "/roms/snes/hacks", &["sfc"]),
+            system("Vide", "", &["sfc"]),
+        ];
+        let name = |rom: &str| find_system_for_rom(&systems, Path::new(rom)).map(|s| s.name.as_str());
+        assert_eq!(name("/roms/snes/Zelda.sfc"), Some("SNES"));
+        assert_eq!(name("/roms/snes/hacks/Zelda Redux.sfc"), Some("SNES hacks"));
+        assert_eq!(name("/roms/snes/hacks/Mario.SMC"), Some("SNES"));
+        assert_eq!(name("/roms/snes/mame.zip"), Some("Arcade"));
+        assert_eq!(name("/roms/snes/notes.txt"), None);
+        // Préfixe de chaîne mais pas de chemin
+        assert_eq!(name("/roms/snes2/Zelda.sfc"), None);
+    }
+
+    #[test]
+    fn finds_rom_art_by_name() {
+        let root = std::env::temp_dir().join(format!("geewers-emulator-{}", std::process::id()));
+        fs::remove_dir_all(&root).ok();
+        let region_dir = root.join("USA");
+        fs::create_dir_all(&region_dir).unwrap();
+        fs::create_dir_all(root.join("Named_Boxarts")).unwrap();
+        fs::create_dir_all(region_dir.join("covers")).unwrap();
+        let rom = region_dir.join("Zelda (USA) [!].sfc");
+        fs::write(&rom, b"").unwrap();
+
+        // Jaquette nommée sans les tags dans un sous-dossier de la racine du système
+        fs::write(root.join("Named_Boxarts").join("Zelda.png"), b"").unwrap();
+        assert_eq!(find_rom_art(&rom, &root), Some(root.join("Named_Boxarts").join("Zelda.png").to_string_lossy().to_string()));
+
+        // Le dossier de la ROM passe avant la racine, la casse est ignorée
+        fs::write(region_dir.join("covers").join("ZELDA (USA) [!].JPG"), b"").unwrap();
+        assert_eq!(find_rom_art(&rom, &root), Some(region_dir.join("covers").join("ZELDA (USA) [!].JPG").to_string_lossy().to_string()));
+
+        // La ROM elle-même n'est pas une image
+        assert_eq!(find_rom_art(&region_dir.join("Metroid.sfc"), &root), None);
+
+        let games = get_system_roms(&system("SNES", &root.to_string_lossy(), &["sfc"]));
+        assert_eq!(games.len(), 1);
+        assert_eq!(games[0].title, "Zelda");
+        assert_eq!(games[0].platform, "SNES");
+        assert_eq!(games[0].id, rom.to_string_lossy());
+        assert!(!games[0].image_path.is_empty());
+
+        fs::remove_dir_all(&root).ok();
+    }
+}
@@ -0,0 +1,49 @@
+use std::fs;
+use std::path::Path;
+use serde_json::Value;
+
+use super::{scavenge_image, GameProvider};
+use crate::models::Game;
+
+pub struct EpicProvider;
+
+impl GameProvider for EpicProvider {
+    fn platform_id(&self) -> &str {
+        "Epic"
+    }
+
+    fn scan(&self) -> Vec<Game> {
+        let mut games = Vec::new();
+        let manifest_path = Path::new("C:\\ProgramData\\Epic\\EpicGamesLauncher\\Data\\Manifests");
+        if manifest_path.exists() {
+            if let Ok(entries) = fs::read_dir(manifest_path) {
+                for entry in entries.flatten() {
+                    if let Ok(content) = fs::read_to_string(entry.path()) {
+                        if let Ok(json) = serde_json::from_str::<Value>(&content) {
+                            let app_name = json["AppName"].as_str().unwrap_or("").to_string();
+                            let display_name = json["DisplayName"].as_str().unwrap_or("").to_string();
+                            let install_loc = json["InstallLocation"].as_str().unwrap_or("").to_string();
+                            if !display_name.is_empty() && app_name != "HelloNeighborModKit" {
+                                let found_img = scavenge_image(&install_loc);
+                                games.push(Game { 
+                                    id: app_name, title: display_name, platform: "Epic".to_string(), 
+                                    image_path: found_img, exe_path: "".to_string(), install_dir: install_loc,
+                                    is_favorite: false, ..Default::default() 
+                                });
+                            }
+                        }
+                    }
+                }
+            }
+        }
+        games
+    }
+
+    fn launch(&self, id: &str, _exe_path: &str) {
+        opener::open(format!("com.epicgames.launcher://apps/{}?action=launch&silent=true", id)).ok();
+    }
+
+    fn open_store_page(&self, _id: &str) {
+        opener::open("com.epicgames.launcher://library").ok();
+    }
+}
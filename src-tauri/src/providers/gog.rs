@@ -0,0 +1,55 @@
+use std::path::Path;
+use std::sync::Arc;
+
+use super::{scavenge_image, GameProvider};
+use crate::models::Game;
+use crate::registry::{Hive, RegistryReader};
+
+pub struct GogProvider {
+    registry: Arc<dyn RegistryReader>,
+}
+
+impl GogProvider {
+    pub fn new(registry: Arc<dyn RegistryReader>) -> Self {
+        Self { registry }
+    }
+}
+
+impl GameProvider for GogProvider {
+    fn platform_id(&self) -> &str {
+        "GOG"
+    }
+
+    fn scan(&self) -> Vec<Game> {
+        let mut games = Vec::new();
+        let keys = ["SOFTWARE\\GOG.com\\Games", "SOFTWARE\\WOW6432Node\\GOG.com\\Games"];
+
+        for key_path in keys {
+            for id in self.registry.subkeys(Hive::LocalMachine, key_path) {
+                let game_key = format!("{}\\{}", key_path, id);
+                let value = |name: &str| self.registry.value(Hive::LocalMachine, &game_key, name).unwrap_or_default();
+                let title = value("gameName");
+                let install_dir = value("path");
+                let exe = value("exe");
+                
+                if !title.is_empty() && !install_dir.is_empty() && Path::new(&install_dir).exists() {
+                    let found_img = scavenge_image(&install_dir);
+                    let full_exe = Path::new(&install_dir).join(exe).to_string_lossy().to_string();
+                    games.push(Game { 
+                        id: id.clone(), title, platform: "GOG".to_string(), 
+                        image_path: found_img, exe_path: full_exe, install_dir, is_favorite: false, ..Default::default() 
+                    });
+                }
+            }
+        }
+        games
+    }
+
+    fn launch(&self, id: &str, _exe_path: &str) {
+        opener::open(format!("goggalaxy://openGameView/{}", id)).ok();
+    }
+
+    fn open_store_page(&self, id: &str) {
+        opener::open(format!("goggalaxy://openGameView/{}", id)).ok();
+    }
+}
@@ -0,0 +1,322 @@
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use serde_json::Value;
+
+use super::{home_dir, legendary, scavenge_image, GameProvider};
+use crate::models::Game;
+
+// ===================== HEROIC GAMES LAUNCHER =====================
+// Heroic gère Epic (via legendary), GOG (via gogdl) et Amazon (via nile).
+// Chaque "runner" a son propre installed.json ; les titres et jaquettes viennent des caches de bibliothèque.
+// L'id d'un jeu est "<runner>/<app_name>", ce qui donne directement l'URL heroic://launch/<runner>/<app_name>.
+
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct HeroicInstall {
+    /// "legendary", "gog" ou "nile"
+    pub runner: String,
+    pub app_name: String,
+    pub title: String,
+    pub install_path: String,
+    pub executable: String,
+    pub is_dlc: bool,
+}
+
+/// Titre et jaquette connus pour un app_name, tirés des caches de bibliothèque de Heroic.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct HeroicLibraryEntry {
+    pub title: String,
+    pub art: String,
+}
+
+pub struct HeroicProvider;
+
+impl GameProvider for HeroicProvider {
+    fn platform_id(&self) -> &str {
+        "Heroic"
+    }
+
+    fn scan(&self) -> Vec<Game> {
+        heroic_config_dirs()
+            .iter()
+            .flat_map(|dir| get_heroic_games_in(dir))
+            .collect()
+    }
+
+    fn launch(&self, id: &str, _exe_path: &str) {
+        opener::open(format!("heroic://launch/{}", id)).ok();
+    }
+
+    fn open_store_page(&self, _id: &str) {
+        opener::open("heroic://").ok();
+    }
+}
+
+/// Dossiers de configuration de Heroic : natif Linux, Flatpak, Windows et macOS.
+pub fn heroic_config_dirs() -> Vec<PathBuf> {
+    let mut candidates = Vec::new();
+    if let Some(xdg) = std::env::var_os("XDG_CONFIG_HOME").filter(|v| !v.is_empty()) {
+        candidates.push(PathBuf::from(xdg).join("heroic"));
+    }
+    if let Some(home) = home_dir() {
+        candidates.push(home.join(".config").join("heroic"));
+        candidates.push(home.join(".var/app/com.heroicgameslauncher.hgl/config/heroic"));
+        candidates.push(home.join("Library/Application Support/heroic"));
+    }
+    if let Some(appdata) = std::env::var_os("APPDATA").filter(|v| !v.is_empty()) {
+        candidates.push(PathBuf::from(appdata).join("heroic"));
+    }
+
+    let mut dirs: Vec<PathBuf> = Vec::new();
+    for dir in candidates {
+        if dir.is_dir() && !dirs.contains(&dir) {
+            dirs.push(dir);
+        }
+    }
+    dirs
+}
+
+/// Jeux installés par un Heroic dont la config est dans `config_dir`.
+pub fn get_heroic_games_in(config_dir: &Path) -> Vec<Game> {
+    let read = |rel: &str| fs::read_to_string(config_dir.join(rel)).unwrap_or_default();
+
+    let mut installs = parse_legendary_installed(&read("legendaryConfig/legendary/installed.json"));
+    installs.extend(parse_gog_installed(&read("gog_store/installed.json")));
+    installs.extend(parse_nile_installed(&read("nile_config/nile/installed.json")));
+
+    // Caches de bibliothèque (emplacements anciens et actuels)
+    let mut library = HashMap::new();
+    for rel in [
+        "store_cache/legendary_library.json",
+        "store_cache/gog_library.json",
+        "store_cache/nile_library.json",
+        "gog_store/library.json",
+        "nile_config/nile/library.json",
+    ] {
+        library.extend(parse_library_cache(&read(rel)));
+    }
+
+    let mut games = Vec::new();
+    for install in installs {
+        if install.is_dlc || install.install_path.is_empty() { continue; }
+        let known = library.get(&install.app_name);
+
+        let title = Some(install.title.clone())
+            .filter(|t| !t.is_empty())
+            .or_else(|| known.map(|k| k.title.clone()).filter(|t| !t.is_empty()))
+            .unwrap_or_else(|| {
+                Path::new(&install.install_path).file_name().unwrap_or_default().to_string_lossy().to_string()
+            });
+
+        let image_path = known
+            .map(|k| k.art.clone())
+            .filter(|a| !a.is_empty())
+            .unwrap_or_else(|| scavenge_image(&install.install_path));
+
+        let exe_path = if install.executable.is_empty() {
+            "".to_string()
+        } else {
+            Path::new(&install.install_path).join(&install.executable).to_string_lossy().to_string()
+        };
+
+        games.push(Game {
+            id: format!("{}/{}", install.runner, install.app_name),
+            title,
+            platform: "Heroic".to_string(),
+            image_path,
+            exe_path,
+            install_dir: install.install_path,
+            ..Default::default()
+        });
+    }
+    games
+}
+
+/// legendaryConfig/legendary/installed.json : même format que le legendary autonome.
+pub fn parse_legendary_installed(content: &str) -> Vec<HeroicInstall> {
+    legendary::parse_installed(content)
+        .into_iter()
+        .map(|g| HeroicInstall {
+            runner: "legendary".to_string(),
+            app_name: g.app_name,
+            title: g.title,
+            install_path: g.install_path,
+            executable: g.executable,
+            is_dlc: g.is_dlc,
+        })
+        .collect()
+}
+
+/// gog_store/installed.json : { "installed": [ { "appName", "install_path", "executable", "is_dlc" } ] }
+pub fn parse_gog_installed(content: &str) -> Vec<HeroicInstall> {
+    let Ok(json) = serde_json::from_str::<Value>(content) else { return Vec::new() };
+    let Some(list) = json["installed"].as_array() else { return Vec::new() };
+
+    list.iter()
+        .filter_map(|entry| {
+            let app_name = entry["appName"].as_str()?;
+            Some(HeroicInstall {
+                runner: "gog".to_string(),
+                app_name: app_name.to_string(),
+                title: "".to_string(),
+                install_path: entry["install_path"].as_str().unwrap_or("").to_string(),
+                executable: entry["executable"].as_str().unwrap_or("").to_string(),
+                is_dlc: entry["is_dlc"].as_bool().unwrap_or(false),
+            })
+        })
+        .collect()
+}
+
+/// nile_config/nile/installed.json : [ { "id", "path" } ]
+pub fn parse_nile_installed(content: &str) -> Vec<HeroicInstall> {
+    let Ok(json) = serde_json::from_str::<Value>(content) else { return Vec::new() };
+    let Some(list) = json.as_array() else { return Vec::new() };
+
+    list.iter()
+        .filter_map(|entry| {
+            let id = entry["id"].as_str()?;
+            Some(HeroicInstall {
+                runner: "nile".to_string(),
+                app_name: id.to_string(),
+                install_path: entry["path"].as_str().unwrap_or("").to_string(),
+                ..Default::default()
+            })
+        })
+        .collect()
+}
+
+/// Caches de bibliothèque : tableaux d'objets sous "library" ou "games" (ou à la racine pour nile).
+/// Les champs varient selon le runner, on prend ce qui existe.
+pub fn parse_library_cache(content: &str) -> HashMap<String, HeroicLibraryEntry> {
+    let mut entries = HashMap::new();
+    let Ok(json) = serde_json::from_str::<Value>(content) else { return entries };
+    let list = json["library"].as_array().or_else(|| json["games"].as_array()).or_else(|| json.as_array());
+    let Some(list) = list else { return entries };
+
+    for item in list {
+        let Some(app_name) = item["app_name"].as_str().or_else(|| item["id"].as_str()) else { continue };
+        let title = item["title"]
+            .as_str()
+            .or_else(|| item["product"]["title"].as_str())
+            .unwrap_or("")
+            .to_string();
+        let art = ["art_square", "art_cover"]
+            .iter()
+            .filter_map(|key| item[*key].as_str())
+            .find(|url| !url.is_empty())
+            .unwrap_or("")
+            .to_string();
+        entries.insert(app_name.to_string(), HeroicLibraryEntry { title, art });
+    }
+    entries
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const LEGENDARY_INSTALLED: &str = r#"{
+        "Fortnite": {
+            "app_name": "Fortnite", "title": "Fortnite", "install_path": "/games/Fortnite",
+            "executable": "FortniteGame/Binaries/Win64/FortniteLauncher.exe", "is_dlc": false
+        },
+        "Sugar": {
+            "app_name": "Sugar", "title": "", "install_path": "/games/Hades",
+            "executable": "x64/Hades.exe", "is_dlc": false
+        },
+        "SugarDLC": {
+            "app_name": "SugarDLC", "title": "Hades Soundtrack", "install_path": "/games/Hades", "is_dlc": true
+        }
+    }"#;
+
+    const GOG_INSTALLED: &str = r#"{
+        "installed": [
+            { "appName": "1207658924", "platform": "windows", "install_path": "/games/Witcher 3",
+              "executable": "bin/x64/witcher3.exe", "is_dlc": false },
+            { "appName": "1207664663", "install_path": "/games/Witcher 3", "is_dlc": true },
+            { "install_path": "/games/sans-id" }
+        ]
+    }"#;
+
+    const NILE_INSTALLED: &str = r#"[
+        { "id": "amzn1.adg.product.1234", "version": "1", "path": "/games/Amazon Game" },
+        { "id": "amzn1.adg.product.5678", "path": "" }
+    ]"#;
+
+    #[test]
+    fn parses_every_runner_installed_json() {
+        let mut legendary = parse_legendary_installed(LEGENDARY_INSTALLED);
+        legendary.sort_by(|a, b| a.app_name.cmp(&b.app_name));
+        assert_eq!(legendary.len(), 3);
+        assert_eq!(legendary[0].runner, "legendary");
+        assert_eq!(legendary[0].executable, "FortniteGame/Binaries/Win64/FortniteLauncher.exe");
+        assert!(legendary[2].is_dlc);
+
+        let gog = parse_gog_installed(GOG_INSTALLED);
+        assert_eq!(gog.len(), 2);
+        assert_eq!(gog[0], HeroicInstall {
+            runner: "gog".to_string(),
+            app_name: "1207658924".to_string(),
+            title: "".to_string(),
+            install_path: "/games/Witcher 3".to_string(),
+            executable: "bin/x64/witcher3.exe".to_string(),
+            is_dlc: false,
+        });
+        assert!(gog[1].is_dlc);
+
+        let nile = parse_nile_installed(NILE_INSTALLED);
+        assert_eq!(nile.len(), 2);
+        assert_eq!(nile[0].runner, "nile");
+        assert_eq!(nile[0].app_name, "amzn1.adg.product.1234");
+        assert_eq!(nile[0].install_path, "/games/Amazon Game");
+
+        assert!(parse_gog_installed("{ pas du json").is_empty());
+        assert!(parse_nile_installed("{}").is_empty());
+    }
+
+    #[test]
+    fn builds_launchable_games_from_a_config_dir() {
+        let dir = std::env::temp_dir().join(format!("geewers-heroic-{}", std::process::id()));
+        fs::remove_dir_all(&dir).ok();
+        let write = |rel: &str, content: &str| {
+            let path = dir.join(rel);
+            fs::create_dir_all(path.parent().unwrap()).unwrap();
+            fs::write(path, content).unwrap();
+        };
+        write("legendaryConfig/legendary/installed.json", LEGENDARY_INSTALLED);
+        write("gog_store/installed.json", GOG_INSTALLED);
+        write("nile_config/nile/installed.json", NILE_INSTALLED);
+        write(
+            "store_cache/legendary_library.json",
+            r#"{ "library": [ { "app_name": "Sugar", "title": "Hades", "art_square": "https://cdn/hades.jpg" } ] }"#,
+        );
+        write(
+            "store_cache/gog_library.json",
+            r#"{ "games": [ { "app_name": "1207658924", "title": "The Witcher 3", "art_square": "", "art_cover": "https://cdn/w3.jpg" } ] }"#,
+        );
+        write(
+            "nile_config/nile/library.json",
+            r#"[ { "id": "amzn1.adg.product.1234", "product": { "title": "Amazon Game" } } ]"#,
+        );
+
+        let mut games = get_heroic_games_in(&dir);
+        games.sort_by(|a, b| a.id.cmp(&b.id));
+        let ids: Vec<&str> = games.iter().map(|g| g.id.as_str()).collect();
+        // DLC et installations sans dossier écartés ; l'id sert tel quel à heroic://launch/<runner>/<app_name>
+        assert_eq!(ids, ["gog/1207658924", "legendary/Fortnite", "legendary/Sugar", "nile/amzn1.adg.product.1234"]);
+
+        let witcher = &games[0];
+        assert_eq!(witcher.title, "The Witcher 3");
+        assert_eq!(witcher.image_path, "https://cdn/w3.jpg");
+        assert_eq!(witcher.exe_path, Path::new("/games/Witcher 3").join("bin/x64/witcher3.exe").to_string_lossy());
+        assert_eq!(witcher.platform, "Heroic");
+
+        // Titre vide dans installed.json : celui du cache de bibliothèque
+        assert_eq!(games[2].title, "Hades");
+        assert_eq!(games[2].image_path, "https://cdn/hades.jpg");
+        assert_eq!(games[3].title, "Amazon Game");
+        assert_eq!(games[3].exe_path, "");
+
+        fs::remove_dir_all(&dir).ok();
+    }
+}
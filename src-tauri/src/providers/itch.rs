@@ -0,0 +1,288 @@
+use std::path::{Path, PathBuf};
+use rusqlite::Connection;
+use serde_json::Value;
+
+use super::{home_dir, open_sqlite_read_only, scavenge_image, GameProvider};
+use crate::models::Game;
+
+// ===================== ITCH.IO =====================
+// L'app itch délègue tout à butler, qui garde une base SQLite (db/butler.db) :
+// - caves : une installation (dossier, exécutables détectés dans "verdict", temps de jeu)
+// - games : titre, jaquette et type de la fiche itch
+// - install_locations : racines d'installation choisies par l'utilisateur
+
+/// Une installation ("cave") telle que décrite par butler.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct ItchCave {
+    pub cave_id: String,
+    pub title: String,
+    pub cover_url: String,
+    /// "game", "tool", "assets", "game_mod", "physical_game", "soundtrack", "other", "comic", "book"
+    pub classification: String,
+    pub install_folder: String,
+    /// Exécutable retenu dans le verdict (chemin absolu), vide si rien de lançable
+    pub launch_target: String,
+    pub seconds_run: u64,
+}
+
+pub struct ItchProvider;
+
+impl GameProvider for ItchProvider {
+    fn platform_id(&self) -> &str {
+        "itch"
+    }
+
+    fn scan(&self) -> Vec<Game> {
+        itch_db_paths()
+            .iter()
+            .filter_map(|db| open_sqlite_read_only(db))
+            .flat_map(|conn| get_itch_games(&conn))
+            .collect()
+    }
+
+    fn launch(&self, id: &str, _exe_path: &str) {
+        opener::open(format!("itch://caves/{}/launch", id)).ok();
+    }
+
+    fn open_store_page(&self, id: &str) {
+        opener::open(format!("itch://caves/{}", id)).ok();
+    }
+}
+
+/// Emplacements de butler.db (Linux, Flatpak, Windows, macOS).
+pub fn itch_db_paths() -> Vec<PathBuf> {
+    let mut candidates = Vec::new();
+    if let Some(appdata) = std::env::var_os("APPDATA").filter(|v| !v.is_empty()) {
+        candidates.push(PathBuf::from(appdata).join("itch"));
+    }
+    if let Some(xdg) = std::env::var_os("XDG_CONFIG_HOME").filter(|v| !v.is_empty()) {
+        candidates.push(PathBuf::from(xdg).join("itch"));
+    }
+    if let Some(home) = home_dir() {
+        candidates.push(home.join(".config").join("itch"));
+        candidates.push(home.join(".var/app/io.itch.itch/config/itch"));
+        candidates.push(home.join("Library/Application Support/itch"));
+    }
+
+    let mut paths: Vec<PathBuf> = Vec::new();
+    for dir in candidates {
+        let db = dir.join("db").join("butler.db");
+        if db.is_file() && !paths.contains(&db) {
+            paths.push(db);
+        }
+    }
+    paths
+}
+
+pub fn get_itch_games(conn: &Connection) -> Vec<Game> {
+    read_caves(conn)
+        .into_iter()
+        // Les fiches "assets", "soundtrack", "book"... ne sont pas jouables
+        .filter(|cave| matches!(cave.classification.as_str(), "" | "game" | "tool" | "game_mod"))
+        .map(|cave| {
+            let image_path = if cave.cover_url.is_empty() { scavenge_image(&cave.install_folder) } else { cave.cover_url };
+            Game {
+                id: cave.cave_id,
+                title: cave.title,
+                platform: "itch".to_string(),
+                image_path,
+                exe_path: cave.launch_target,
+                install_dir: cave.install_folder,
+                playtime_minutes: cave.seconds_run / 60,
+                is_tool: cave.classification == "tool",
+                ..Default::default()
+            }
+        })
+        .collect()
+}
+
+pub fn read_caves(conn: &Connection) -> Vec<ItchCave> {
+    let query = "SELECT c.id, g.title, g.cover_url, g.still_cover_url, g.classification, \
+                        c.verdict, c.install_folder_name, c.custom_install_folder, l.path, c.seconds_run \
+                 FROM caves c \
+                 JOIN games g ON g.id = c.game_id \
+                 LEFT JOIN install_locations l ON l.id = c.install_location_id";
+    let Ok(mut stmt) = conn.prepare(query) else { return Vec::new() };
+
+    let rows = stmt.query_map([], |row| {
+        let text = |i: usize| row.get::<_, Option<String>>(i).map(Option::unwrap_or_default);
+        let cover = text(2)?;
+        let still_cover = text(3)?;
+        let verdict = text(5)?;
+        let folder_name = text(6)?;
+        let custom_folder = text(7)?;
+        let location = text(8)?;
+
+        // Dossier d'installation : dossier personnalisé, sinon emplacement + nom de dossier, sinon basePath du verdict
+        let verdict_json = serde_json::from_str::<Value>(&verdict).unwrap_or(Value::Null);
+        let install_folder = if !custom_folder.is_empty() {
+            custom_folder
+        } else if !location.is_empty() && !folder_name.is_empty() {
+            Path::new(&location).join(&folder_name).to_string_lossy().to_string()
+        } else {
+            verdict_json["basePath"].as_str().unwrap_or("").to_string()
+        };
+
+        Ok(ItchCave {
+            cave_id: row.get(0)?,
+            title: text(1)?,
+            // Les jaquettes animées (gif) ont une version fixe plus adaptée à la grille
+            cover_url: if still_cover.is_empty() { cover } else { still_cover },
+            classification: text(4)?,
+            launch_target: pick_launch_target(&verdict_json, &install_folder).unwrap_or_default(),
+            install_folder,
+            seconds_run: row.get::<_, Option<i64>>(9)?.unwrap_or(0).max(0) as u64,
+        })
+    });
+    match rows {
+        Ok(rows) => rows.flatten().filter(|c| !c.title.is_empty()).collect(),
+        Err(_) => Vec::new(),
+    }
+}
+
+/// Choisit l'exécutable à lancer parmi les "candidates" du verdict de butler,
+/// en privilégiant ceux prévus pour l'OS courant.
+pub fn pick_launch_target(verdict: &Value, install_folder: &str) -> Option<String> {
+    let candidates = verdict["candidates"].as_array()?;
+    let native: &[&str] = if cfg!(windows) {
+        &["windows", "windows-script", "msi"]
+    } else if cfg!(target_os = "macos") {
+        &["app-macos", "macos", "script"]
+    } else {
+        &["linux", "script"]
+    };
+    let portable = ["html", "jar", "love"];
+
+    let flavor = |c: &&Value| c["flavor"].as_str().unwrap_or("").to_string();
+    let chosen = candidates
+        .iter()
+        .find(|c| native.contains(&flavor(c).as_str()))
+        .or_else(|| candidates.iter().find(|c| portable.contains(&flavor(c).as_str())))
+        .or_else(|| candidates.first())?;
+
+    let relative = chosen["path"].as_str().filter(|p| !p.is_empty())?;
+    let base = verdict["basePath"].as_str().filter(|b| !b.is_empty()).unwrap_or(install_folder);
+    Some(Path::new(base).join(relative).to_string_lossy().to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    // Flavor natif de l'OS qui exécute les tests
+    fn native_flavor() -> &'static str {
+        if cfg!(windows) {
+            "windows"
+        } else if cfg!(target_os = "macos") {
+            "macos"
+        } else {
+            "linux"
+        }
+    }
+
+    fn native_verdict(base_path: &str) -> String {
+        json!({
+            "basePath": base_path,
+            "candidates": [
+                { "path": "index.html", "flavor": "html" },
+                { "path": "Game.x86_64", "flavor": native_flavor() },
+            ]
+        })
+        .to_string()
+    }
+
+    // id, game_id, verdict, install_folder_name, custom_install_folder, install_location_id, seconds_run
+    type CaveRow = (&'static str, i64, String, Option<&'static str>, Option<&'static str>, Option<&'static str>, i64);
+
+    // Tables de butler.db réduites aux colonnes lues
+    fn butler_db() -> Connection {
+        let conn = Connection::open_in_memory().unwrap();
+        conn.execute_batch(
+            "CREATE TABLE games (id INTEGER PRIMARY KEY, title TEXT, cover_url TEXT, still_cover_url TEXT, classification TEXT);
+             CREATE TABLE install_locations (id TEXT PRIMARY KEY, path TEXT);
+             CREATE TABLE caves (id TEXT PRIMARY KEY, game_id INTEGER, verdict TEXT, install_folder_name TEXT,
+                                 custom_install_folder TEXT, install_location_id TEXT, seconds_run INTEGER);
+             INSERT INTO install_locations VALUES ('loc', '/home/user/.config/itch/apps');
+             INSERT INTO games VALUES (1, 'Perso', 'https://img/perso.gif', 'https://img/perso.png', 'game');
+             INSERT INTO games VALUES (2, 'Emplacement', 'https://img/loc.png', NULL, 'game');
+             INSERT INTO games VALUES (3, 'Verdict', NULL, NULL, 'tool');
+             INSERT INTO games VALUES (4, 'Bande originale', NULL, NULL, 'soundtrack');
+             INSERT INTO games VALUES (5, NULL, NULL, NULL, 'game');",
+        )
+        .unwrap();
+        let caves: [CaveRow; 5] = [
+            ("cave-custom", 1, native_verdict("/mnt/jeux/perso"), Some("perso"), Some("/mnt/jeux/perso"), Some("loc"), 3600),
+            ("cave-location", 2, json!({ "candidates": [] }).to_string(), Some("emplacement"), None, Some("loc"), 0),
+            ("cave-verdict", 3, native_verdict("/opt/itch/verdict"), None, None, None, 90),
+            ("cave-ost", 4, native_verdict("/opt/itch/ost"), None, None, None, 0),
+            ("cave-sans-titre", 5, native_verdict("/opt/itch/sans-titre"), None, None, None, 0),
+        ];
+        for (id, game_id, verdict, folder, custom, location, seconds) in caves {
+            conn.execute(
+                "INSERT INTO caves VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)",
+                rusqlite::params![id, game_id, verdict, folder, custom, location, seconds],
+            )
+            .unwrap();
+        }
+        conn
+    }
+
+    #[test]
+    fn resolves_install_folders_in_order() {
+        let mut caves = read_caves(&butler_db());
+        caves.sort_by(|a, b| a.cave_id.cmp(&b.cave_id));
+        // La cave sans titre est écartée
+        let ids: Vec<&str> = caves.iter().map(|c| c.cave_id.as_str()).collect();
+        assert_eq!(ids, ["cave-custom", "cave-location", "cave-ost", "cave-verdict"]);
+
+        // 1. Dossier personnalisé, même si un emplacement est aussi renseigné
+        assert_eq!(caves[0].install_folder, "/mnt/jeux/perso");
+        assert_eq!(caves[0].cover_url, "https://img/perso.png");
+        assert_eq!(caves[0].launch_target, Path::new("/mnt/jeux/perso").join("Game.x86_64").to_string_lossy());
+        assert_eq!(caves[0].seconds_run, 3600);
+        // 2. Emplacement d'installation + nom du dossier
+        assert_eq!(caves[1].install_folder, Path::new("/home/user/.config/itch/apps").join("emplacement").to_string_lossy());
+        assert_eq!(caves[1].cover_url, "https://img/loc.png");
+        assert_eq!(caves[1].launch_target, "");
+        // 3. basePath du verdict
+        assert_eq!(caves[3].install_folder, "/opt/itch/verdict");
+        assert_eq!(caves[3].classification, "tool");
+
+        // Base sans tables butler : aucune cave
+        assert!(read_caves(&Connection::open_in_memory().unwrap()).is_empty());
+    }
+
+    #[test]
+    fn keeps_playable_classifications_only() {
+        let mut games = get_itch_games(&butler_db());
+        games.sort_by(|a, b| a.id.cmp(&b.id));
+        let ids: Vec<&str> = games.iter().map(|g| g.id.as_str()).collect();
+        assert_eq!(ids, ["cave-custom", "cave-location", "cave-verdict"]);
+        assert_eq!(games[0].playtime_minutes, 60);
+        assert!(!games[0].is_tool);
+        assert!(games[2].is_tool);
+    }
+
+    #[test]
+    fn picks_native_then_portable_then_first_candidate() {
+        // Le flavor natif passe devant le HTML, quel que soit l'ordre du verdict
+        let verdict: Value = serde_json::from_str(&native_verdict("/base")).unwrap();
+        assert_eq!(pick_launch_target(&verdict, "/ignored"), Some(Path::new("/base").join("Game.x86_64").to_string_lossy().to_string()));
+
+        // Pas de natif : un format portable plutôt que le premier candidat
+        let verdict = json!({ "candidates": [
+            { "path": "Game.exe", "flavor": if cfg!(windows) { "linux" } else { "windows" } },
+            { "path": "game.love", "flavor": "love" },
+        ] });
+        assert_eq!(pick_launch_target(&verdict, "/install"), Some(Path::new("/install").join("game.love").to_string_lossy().to_string()));
+
+        // Ni natif ni portable : le premier candidat
+        let verdict = json!({ "basePath": "", "candidates": [ { "path": "setup.bin", "flavor": "unknown" } ] });
+        assert_eq!(pick_launch_target(&verdict, "/install"), Some(Path::new("/install").join("setup.bin").to_string_lossy().to_string()));
+
+        assert_eq!(pick_launch_target(&json!({ "candidates": [] }), "/install"), None);
+        assert_eq!(pick_launch_target(&json!({ "candidates": [ { "path": "", "flavor": "linux" } ] }), "/install"), None);
+        assert_eq!(pick_launch_target(&Value::Null, "/install"), None);
+    }
+}
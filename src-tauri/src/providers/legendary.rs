@@ -0,0 +1,177 @@
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use serde_json::Value;
+
+use super::{home_dir, scavenge_image, GameProvider};
+use crate::models::Game;
+
+// ===================== LEGENDARY (EPIC EN LIGNE DE COMMANDE) =====================
+// installed.json : { "<app_name>": { "app_name", "title", "install_path", "executable", "launch_parameters", "is_dlc" } }
+// Heroic embarque sa propre copie de legendary et réutilise le même format.
+
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct LegendaryGame {
+    pub app_name: String,
+    pub title: String,
+    pub install_path: String,
+    /// Chemin relatif à install_path
+    pub executable: String,
+    pub launch_parameters: String,
+    pub is_dlc: bool,
+}
+
+pub struct LegendaryProvider;
+
+impl GameProvider for LegendaryProvider {
+    fn platform_id(&self) -> &str {
+        "Legendary"
+    }
+
+    fn scan(&self) -> Vec<Game> {
+        legendary_config_dir()
+            .map(|dir| get_legendary_games_in(&dir))
+            .unwrap_or_default()
+    }
+
+    // On passe par `legendary launch` (authentification, overlay, paramètres...) ;
+    // si la commande n'est pas dans le PATH, on lance directement l'exécutable.
+    fn launch(&self, id: &str, exe_path: &str) {
+        if Command::new("legendary").args(["launch", id]).spawn().is_err() && !exe_path.is_empty() {
+            opener::open(exe_path).ok();
+        }
+    }
+
+    fn open_store_page(&self, _id: &str) {
+        opener::open("https://store.epicgames.com/").ok();
+    }
+}
+
+/// Dossier de config de legendary : $LEGENDARY_CONFIG_PATH, sinon $XDG_CONFIG_HOME/legendary ou ~/.config/legendary.
+pub fn legendary_config_dir() -> Option<PathBuf> {
+    let env_dir = |name: &str| std::env::var_os(name).filter(|v| !v.is_empty()).map(PathBuf::from);
+    let candidates = [
+        env_dir("LEGENDARY_CONFIG_PATH"),
+        env_dir("XDG_CONFIG_HOME").map(|d| d.join("legendary")),
+        home_dir().map(|h| h.join(".config").join("legendary")),
+    ];
+    candidates.into_iter().flatten().find(|d| d.join("installed.json").is_file())
+}
+
+pub fn get_legendary_games_in(config_dir: &Path) -> Vec<Game> {
+    let content = std::fs::read_to_string(config_dir.join("installed.json")).unwrap_or_default();
+
+    parse_installed(&content)
+        .into_iter()
+        .filter(|g| !g.is_dlc && !g.install_path.is_empty())
+        .map(|g| {
+            let exe_path = if g.executable.is_empty() {
+                "".to_string()
+            } else {
+                Path::new(&g.install_path).join(&g.executable).to_string_lossy().to_string()
+            };
+            let title = if g.title.is_empty() { g.app_name.clone() } else { g.title };
+            Game {
+                image_path: scavenge_image(&g.install_path),
+                id: g.app_name,
+                title,
+                platform: "Legendary".to_string(),
+                exe_path,
+                install_dir: g.install_path,
+                launch_options: g.launch_parameters,
+                ..Default::default()
+            }
+        })
+        .collect()
+}
+
+pub fn parse_installed(content: &str) -> Vec<LegendaryGame> {
+    let Ok(json) = serde_json::from_str::<Value>(content) else { return Vec::new() };
+    let Some(map) = json.as_object() else { return Vec::new() };
+
+    map.iter()
+        .map(|(key, entry)| LegendaryGame {
+            app_name: entry["app_name"].as_str().unwrap_or(key).to_string(),
+            title: entry["title"].as_str().unwrap_or("").to_string(),
+            install_path: entry["install_path"].as_str().unwrap_or("").to_string(),
+            executable: entry["executable"].as_str().unwrap_or("").to_string(),
+            launch_parameters: entry["launch_parameters"].as_str().unwrap_or("").to_string(),
+            is_dlc: entry["is_dlc"].as_bool().unwrap_or(false),
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+
+    // installed.json tel qu'écrit par legendary (champs non lus compris)
+    const INSTALLED: &str = r#"{
+        "Sugar": {
+            "app_name": "Sugar", "title": "Hades", "version": "1.38290", "install_path": "/games/Hades",
+            "executable": "x64/Hades.exe", "launch_parameters": "-vulkan", "is_dlc": false,
+            "platform": "Windows", "can_run_offline": true
+        },
+        "SugarSoundtrack": {
+            "app_name": "SugarSoundtrack", "title": "Hades Soundtrack", "install_path": "/games/Hades", "is_dlc": true
+        },
+        "Quail": { "title": "", "install_path": "/games/Quail" },
+        "NotInstalled": { "app_name": "NotInstalled", "title": "Nulle part", "install_path": "" }
+    }"#;
+
+    #[test]
+    fn parses_installed_json() {
+        let mut games = parse_installed(INSTALLED);
+        games.sort_by(|a, b| a.app_name.cmp(&b.app_name));
+        assert_eq!(games.len(), 4);
+        assert_eq!(games[2], LegendaryGame {
+            app_name: "Sugar".to_string(),
+            title: "Hades".to_string(),
+            install_path: "/games/Hades".to_string(),
+            executable: "x64/Hades.exe".to_string(),
+            launch_parameters: "-vulkan".to_string(),
+            is_dlc: false,
+        });
+        assert!(games[3].is_dlc);
+        // Sans champ app_name, la clé de l'objet en tient lieu
+        assert_eq!(games[1].app_name, "Quail");
+    }
+
+    #[test]
+    fn rejects_malformed_installed_json() {
+        assert!(parse_installed("").is_empty());
+        assert!(parse_installed(&INSTALLED[..INSTALLED.len() / 2]).is_empty());
+        assert!(parse_installed("[1, 2, 3]").is_empty());
+    }
+
+    #[test]
+    fn builds_games_from_config_dir() {
+        let dir = std::env::temp_dir().join(format!("geewers-legendary-{}", std::process::id()));
+        fs::remove_dir_all(&dir).ok();
+        fs::create_dir_all(&dir).unwrap();
+        fs::write(dir.join("installed.json"), INSTALLED).unwrap();
+
+        let mut games = get_legendary_games_in(&dir);
+        games.sort_by(|a, b| a.id.cmp(&b.id));
+        // Le DLC et le jeu sans dossier d'installation sont écartés
+        let ids: Vec<&str> = games.iter().map(|g| g.id.as_str()).collect();
+        assert_eq!(ids, ["Quail", "Sugar"]);
+
+        // Titre vide : l'app_name en tient lieu, et pas d'exécutable à joindre
+        assert_eq!(games[0].title, "Quail");
+        assert_eq!(games[0].exe_path, "");
+
+        let hades = &games[1];
+        assert_eq!(hades.title, "Hades");
+        assert_eq!(hades.platform, "Legendary");
+        assert_eq!(hades.exe_path, Path::new("/games/Hades").join("x64/Hades.exe").to_string_lossy());
+        assert_eq!(hades.install_dir, "/games/Hades");
+        assert_eq!(hades.launch_options, "-vulkan");
+
+        // installed.json corrompu : aucun jeu plutôt qu'une erreur
+        fs::write(dir.join("installed.json"), "{ \"Sugar\": ").unwrap();
+        assert!(get_legendary_games_in(&dir).is_empty());
+
+        fs::remove_dir_all(&dir).ok();
+    }
+}
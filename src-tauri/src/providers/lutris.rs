@@ -0,0 +1,259 @@
+use std::path::{Path, PathBuf};
+use rusqlite::Connection;
+
+use super::{home_dir, open_sqlite_read_only, scavenge_image, GameProvider};
+use crate::models::Game;
+
+// ===================== LUTRIS =====================
+// La bibliothèque est dans pga.db (table "games"), la config de chaque jeu dans un YAML
+// (games/<configpath>.yml) et les jaquettes dans coverart/ et banners/ (<slug>.jpg).
+
+/// Emplacements d'une installation de Lutris (native ou Flatpak).
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct LutrisPaths {
+    pub db: PathBuf,
+    /// Dossiers pouvant contenir games/<configpath>.yml
+    pub config_dirs: Vec<PathBuf>,
+    /// Dossiers pouvant contenir coverart/ et banners/
+    pub art_dirs: Vec<PathBuf>,
+}
+
+/// Ligne de la table `games` de pga.db.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct LutrisGame {
+    pub id: i64,
+    pub name: String,
+    pub slug: String,
+    pub runner: String,
+    pub directory: String,
+    pub configpath: String,
+    pub lastplayed: u64,
+    /// Lutris stocke le temps de jeu en heures
+    pub playtime_hours: f64,
+}
+
+pub struct LutrisProvider;
+
+impl GameProvider for LutrisProvider {
+    fn platform_id(&self) -> &str {
+        "Lutris"
+    }
+
+    fn scan(&self) -> Vec<Game> {
+        let Some(home) = home_dir() else { return Vec::new() };
+        lutris_installs(&home)
+            .iter()
+            .flat_map(get_lutris_games)
+            .collect()
+    }
+
+    fn launch(&self, id: &str, _exe_path: &str) {
+        opener::open(format!("lutris:rungameid/{}", id)).ok();
+    }
+
+    fn open_store_page(&self, _id: &str) {
+        opener::open("lutris:").ok();
+    }
+}
+
+/// Installations de Lutris présentes dans le dossier personnel.
+pub fn lutris_installs(home: &Path) -> Vec<LutrisPaths> {
+    let native = LutrisPaths {
+        db: home.join(".local/share/lutris/pga.db"),
+        config_dirs: vec![home.join(".config/lutris"), home.join(".local/share/lutris")],
+        art_dirs: vec![home.join(".local/share/lutris"), home.join(".cache/lutris")],
+    };
+    let flatpak_root = home.join(".var/app/net.lutris.Lutris");
+    let flatpak = LutrisPaths {
+        db: flatpak_root.join("data/lutris/pga.db"),
+        config_dirs: vec![flatpak_root.join("config/lutris"), flatpak_root.join("data/lutris")],
+        art_dirs: vec![flatpak_root.join("data/lutris"), flatpak_root.join("cache/lutris")],
+    };
+    [native, flatpak].into_iter().filter(|p| p.db.is_file()).collect()
+}
+
+pub fn get_lutris_games(paths: &LutrisPaths) -> Vec<Game> {
+    let Some(conn) = open_sqlite_read_only(&paths.db) else { return Vec::new() };
+
+    read_installed_games(&conn)
+        .into_iter()
+        .map(|lutris| {
+            let exe_path = read_game_exe(paths, &lutris).unwrap_or_default();
+            let image_path = find_art(paths, &lutris.slug).unwrap_or_else(|| scavenge_image(&lutris.directory));
+            Game {
+                id: lutris.id.to_string(),
+                title: lutris.name,
+                platform: "Lutris".to_string(),
+                image_path,
+                exe_path,
+                install_dir: lutris.directory,
+                playtime_minutes: (lutris.playtime_hours * 60.0).round() as u64,
+                last_played: lutris.lastplayed,
+                ..Default::default()
+            }
+        })
+        .collect()
+}
+
+/// Jeux marqués installés dans pga.db.
+pub fn read_installed_games(conn: &Connection) -> Vec<LutrisGame> {
+    let query = "SELECT id, name, slug, runner, directory, configpath, lastplayed, playtime \
+                 FROM games WHERE installed = 1";
+    let Ok(mut stmt) = conn.prepare(query) else { return Vec::new() };
+    let rows = stmt.query_map([], |row| {
+        Ok(LutrisGame {
+            id: row.get(0)?,
+            name: row.get::<_, Option<String>>(1)?.unwrap_or_default(),
+            slug: row.get::<_, Option<String>>(2)?.unwrap_or_default(),
+            runner: row.get::<_, Option<String>>(3)?.unwrap_or_default(),
+            directory: row.get::<_, Option<String>>(4)?.unwrap_or_default(),
+            configpath: row.get::<_, Option<String>>(5)?.unwrap_or_default(),
+            lastplayed: row.get::<_, Option<i64>>(6)?.unwrap_or(0).max(0) as u64,
+            playtime_hours: row.get::<_, Option<f64>>(7)?.unwrap_or(0.0),
+        })
+    });
+    match rows {
+        Ok(rows) => rows.flatten().filter(|g| !g.name.is_empty()).collect(),
+        Err(_) => Vec::new(),
+    }
+}
+
+/// Exécutable déclaré dans games/<configpath>.yml (clé game.exe), rendu absolu si besoin.
+fn read_game_exe(paths: &LutrisPaths, game: &LutrisGame) -> Option<String> {
+    if game.configpath.is_empty() { return None; }
+    let content = paths
+        .config_dirs
+        .iter()
+        .map(|dir| dir.join("games").join(format!("{}.yml", game.configpath)))
+        .find_map(|p| std::fs::read_to_string(p).ok())?;
+    let exe = parse_game_exe(&content)?;
+
+    let exe_path = Path::new(&exe);
+    if exe_path.is_absolute() || game.directory.is_empty() {
+        Some(exe)
+    } else {
+        Some(Path::new(&game.directory).join(exe_path).to_string_lossy().to_string())
+    }
+}
+
+/// Valeur de `game: exe:` dans un YAML de config Lutris.
+pub fn parse_game_exe(yaml: &str) -> Option<String> {
+    let doc: serde_yaml::Value = serde_yaml::from_str(yaml).ok()?;
+    let exe = doc.get("game")?.get("exe")?.as_str()?;
+    if exe.is_empty() { None } else { Some(exe.to_string()) }
+}
+
+// Jaquette portrait (coverart) en priorité, sinon la bannière
+fn find_art(paths: &LutrisPaths, slug: &str) -> Option<String> {
+    if slug.is_empty() { return None; }
+    ["coverart", "banners"].iter().find_map(|kind| {
+        paths.art_dirs.iter().find_map(|dir| {
+            ["jpg", "png"]
+                .iter()
+                .map(|ext| dir.join(kind).join(format!("{}.{}", slug, ext)))
+                .find(|p| p.is_file())
+                .map(|p| p.to_string_lossy().to_string())
+        })
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+
+    // Table games de pga.db réduite aux colonnes lues, plus "installed"
+    fn games_table(conn: &Connection, directory: &str) {
+        conn.execute_batch(
+            "CREATE TABLE games (id INTEGER PRIMARY KEY, name TEXT, slug TEXT, runner TEXT, directory TEXT, \
+             configpath TEXT, lastplayed INTEGER, playtime REAL, installed INTEGER);",
+        )
+        .unwrap();
+        conn.execute(
+            "INSERT INTO games VALUES (1, 'Celeste', 'celeste', 'linux', ?1, 'celeste-1700000000', 1700000000, 2.5, 1)",
+            [directory],
+        )
+        .unwrap();
+        conn.execute_batch(
+            "INSERT INTO games VALUES (2, 'Désinstallé', 'old', 'wine', '/games/old', 'old-1', 0, 10.0, 0);
+             INSERT INTO games VALUES (3, 'Sans métadonnées', NULL, NULL, NULL, NULL, NULL, NULL, 1);
+             INSERT INTO games VALUES (4, NULL, 'sans-nom', 'wine', '/games/sans-nom', NULL, NULL, NULL, 1);",
+        )
+        .unwrap();
+    }
+
+    #[test]
+    fn reads_installed_games_only() {
+        let conn = Connection::open_in_memory().unwrap();
+        games_table(&conn, "/games/celeste");
+
+        let games = read_installed_games(&conn);
+        assert_eq!(
+            games,
+            vec![
+                LutrisGame {
+                    id: 1,
+                    name: "Celeste".to_string(),
+                    slug: "celeste".to_string(),
+                    runner: "linux".to_string(),
+                    directory: "/games/celeste".to_string(),
+                    configpath: "celeste-1700000000".to_string(),
+                    lastplayed: 1_700_000_000,
+                    playtime_hours: 2.5,
+                },
+                // Colonnes NULL : valeurs par défaut plutôt qu'une ligne perdue
+                LutrisGame { id: 3, name: "Sans métadonnées".to_string(), ..Default::default() },
+            ]
+        );
+        // Base sans table games : aucun jeu
+        assert!(read_installed_games(&Connection::open_in_memory().unwrap()).is_empty());
+    }
+
+    #[test]
+    fn parses_game_exe_from_yaml() {
+        assert_eq!(
+            parse_game_exe("game:\n  exe: drive_c/Celeste/Celeste.exe\n  prefix: /games/celeste\nwine:\n  version: lutris-7.2\n"),
+            Some("drive_c/Celeste/Celeste.exe".to_string())
+        );
+        assert_eq!(parse_game_exe("game:\n  exe: ''\n"), None);
+        assert_eq!(parse_game_exe("system:\n  env: {}\n"), None);
+        assert_eq!(parse_game_exe("game: [pas: du: yaml"), None);
+    }
+
+    #[test]
+    fn builds_games_with_exe_art_and_playtime() {
+        let root = std::env::temp_dir().join(format!("geewers-lutris-{}", std::process::id()));
+        fs::remove_dir_all(&root).ok();
+        let config_dir = root.join("config");
+        let art_dir = root.join("data");
+        let game_dir = root.join("games").join("celeste");
+        fs::create_dir_all(config_dir.join("games")).unwrap();
+        fs::create_dir_all(art_dir.join("coverart")).unwrap();
+        fs::create_dir_all(art_dir.join("banners")).unwrap();
+        fs::write(config_dir.join("games").join("celeste-1700000000.yml"), "game:\n  exe: bin/Celeste\n").unwrap();
+        fs::write(art_dir.join("banners").join("celeste.jpg"), b"").unwrap();
+        fs::write(art_dir.join("coverart").join("celeste.jpg"), b"").unwrap();
+
+        let paths = LutrisPaths {
+            db: root.join("pga.db"),
+            config_dirs: vec![config_dir],
+            art_dirs: vec![art_dir.clone()],
+        };
+        games_table(&Connection::open(&paths.db).unwrap(), &game_dir.to_string_lossy());
+
+        let games = get_lutris_games(&paths);
+        assert_eq!(games.len(), 2);
+        let celeste = &games[0];
+        assert_eq!(celeste.id, "1");
+        // game.exe relatif, résolu par rapport au dossier du jeu
+        assert_eq!(celeste.exe_path, game_dir.join("bin/Celeste").to_string_lossy());
+        // La jaquette portrait passe avant la bannière
+        assert_eq!(celeste.image_path, art_dir.join("coverart").join("celeste.jpg").to_string_lossy());
+        // 2,5 heures dans pga.db
+        assert_eq!(celeste.playtime_minutes, 150);
+        assert_eq!(celeste.last_played, 1_700_000_000);
+        assert_eq!(games[1].exe_path, "");
+
+        fs::remove_dir_all(&root).ok();
+    }
+}
@@ -0,0 +1,339 @@
+use std::collections::HashSet;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+use crate::models::{EmulatorSystem, Game, UserData};
+use crate::registry::{default_registry, RegistryReader};
+
+mod custom;
+mod amazon;
+mod battlenet;
+mod desktop;
+mod ea;
+mod emulator;
+mod epic;
+mod gog;
+mod heroic;
+mod itch;
+mod legendary;
+mod lutris;
+mod retroarch;
+mod rockstar;
+pub mod steam;
+mod ubisoft;
+mod xbox;
+
+pub use amazon::{get_amazon_games, AmazonProvider};
+pub use battlenet::{get_battlenet_games, parse_product_db, BattleNetInstall, BattleNetProvider};
+pub use custom::CustomProvider;
+pub use desktop::{desktop_entry_files, parse_desktop_entry, DesktopEntry, DesktopEntryProvider};
+pub use ea::EaProvider;
+pub use emulator::{build_launch_command, get_system_roms, EmulatorProvider};
+pub use epic::EpicProvider;
+pub use gog::GogProvider;
+pub use heroic::{get_heroic_games_in, heroic_config_dirs, HeroicProvider};
+pub use itch::{get_itch_games, itch_db_paths, ItchProvider};
+pub use legendary::{get_legendary_games_in, legendary_config_dir, LegendaryProvider};
+pub use lutris::{get_lutris_games, lutris_installs, LutrisPaths, LutrisProvider};
+pub use retroarch::{get_retroarch_games, retroarch_installs, RetroArchInstall, RetroArchProvider};
+pub use rockstar::RockstarProvider;
+pub use steam::SteamProvider;
+pub use ubisoft::UbisoftProvider;
+pub use xbox::{get_xbox_games, XboxProvider};
+
+// ===================== CONTRAT D'UN PROVIDER =====================
+
+/// Une source de jeux (boutique, launcher, outil maison...).
+/// Chaque boutique vit dans son propre module et s'enregistre dans le `ProviderRegistry`.
+pub trait GameProvider: Send + Sync {
+    /// Identifiant de plateforme, identique au champ `Game.platform` (ex: "Steam").
+    fn platform_id(&self) -> &str;
+
+    /// Liste les jeux installés. Ne doit jamais paniquer : en cas d'erreur on renvoie ce qu'on a trouvé.
+    fn scan(&self) -> Vec<Game>;
+
+    /// Scan pour les sources qui dépendent des réglages de l'utilisateur (disques sélectionnés...).
+    /// Par défaut les réglages sont ignorés.
+    fn scan_with_settings(&self, _settings: &UserData) -> Vec<Game> {
+        self.scan()
+    }
+
+    /// Lance le jeu. Par défaut on ouvre directement l'exécutable.
+    fn launch(&self, _id: &str, exe_path: &str) {
+        opener::open(exe_path).ok();
+    }
+
+    /// Lancement pour les sources qui dépendent des réglages (commande d'émulateur...).
+    /// Par défaut les réglages sont ignorés.
+    fn launch_with_settings(&self, id: &str, exe_path: &str, _settings: &UserData) {
+        self.launch(id, exe_path);
+    }
+
+    /// Ouvre la page du jeu dans le launcher de la boutique.
+    fn open_store_page(&self, id: &str);
+
+    /// (ancien id, id actuel) des jeux scannés dont l'id a changé d'une version du Hub à l'autre,
+    /// pour reprendre les favoris enregistrés sous l'ancien id. Par défaut aucun.
+    fn legacy_ids(&self, _games: &[Game]) -> Vec<(String, String)> {
+        Vec::new()
+    }
+}
+
+// ===================== REGISTRE =====================
+
+#[derive(Default)]
+pub struct ProviderRegistry {
+    providers: Vec<Box<dyn GameProvider>>,
+}
+
+impl ProviderRegistry {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Registre avec toutes les boutiques gérées nativement par le Hub.
+    pub fn with_defaults() -> Self {
+        Self::with_windows_registry(default_registry())
+    }
+
+    /// Comme `with_defaults`, mais en lisant le registre Windows fourni (ex: un `MemoryRegistry` de test).
+    pub fn with_windows_registry(windows_registry: Arc<dyn RegistryReader>) -> Self {
+        let mut registry = Self::new();
+        registry.register(SteamProvider::new(windows_registry.clone()));
+        registry.register(EpicProvider);
+        registry.register(GogProvider::new(windows_registry.clone()));
+        registry.register(EaProvider::new(windows_registry.clone()));
+        registry.register(UbisoftProvider::new(windows_registry.clone()));
+        registry.register(BattleNetProvider::new(windows_registry.clone()));
+        registry.register(RockstarProvider::new(windows_registry));
+        registry.register(AmazonProvider::new());
+        registry.register(XboxProvider);
+        registry.register(HeroicProvider);
+        registry.register(LutrisProvider);
+        registry.register(LegendaryProvider);
+        registry.register(ItchProvider);
+        registry.register(RetroArchProvider);
+        registry.register(DesktopEntryProvider);
+        registry.register(EmulatorProvider);
+        registry.register(CustomProvider);
+        registry
+    }
+
+    /// Ajoute un provider. Si un provider avec le même `platform_id` existe déjà, il est remplacé.
+    pub fn register<P: GameProvider + 'static>(&mut self, provider: P) {
+        self.providers.retain(|p| p.platform_id() != provider.platform_id());
+        self.providers.push(Box::new(provider));
+    }
+
+    pub fn get(&self, platform: &str) -> Option<&dyn GameProvider> {
+        self.providers
+            .iter()
+            .find(|p| p.platform_id() == platform)
+            .map(|p| p.as_ref())
+    }
+
+    /// Provider responsable d'une plateforme. Les jeux d'émulation ont pour plateforme
+    /// le nom de leur système, qui est géré par le provider "Emulator".
+    pub fn get_for(&self, platform: &str, settings: &UserData) -> Option<&dyn GameProvider> {
+        self.get(platform).or_else(|| {
+            if settings.emulator_systems.iter().any(|s| s.name.trim().eq_ignore_ascii_case(platform.trim())) {
+                self.get("Emulator")
+            } else {
+                None
+            }
+        })
+    }
+
+    /// Refuse les systèmes d'émulation nommés comme une plateforme existante ("Steam", "Xbox"...) :
+    /// `get_for` confierait leurs jeux au provider de cette plateforme au lieu de l'émulateur.
+    pub fn check_emulator_systems(&self, systems: &[EmulatorSystem]) -> Result<(), String> {
+        for system in systems {
+            if self.providers.iter().any(|p| p.platform_id().eq_ignore_ascii_case(system.name.trim())) {
+                return Err(format!("Emulator system name \"{}\" is already used by a platform", system.name));
+            }
+        }
+        Ok(())
+    }
+
+    /// Remplace dans les favoris les anciens ids par les ids actuels. Renvoie true si un favori a changé.
+    pub fn migrate_favorites(&self, games: &[Game], favorites: &mut HashSet<String>) -> bool {
+        let mut changed = false;
+        for provider in &self.providers {
+            let platform = provider.platform_id();
+            for (old_id, new_id) in provider.legacy_ids(games) {
+                if favorites.remove(&format!("{}-{}", platform, old_id)) {
+                    favorites.insert(format!("{}-{}", platform, new_id));
+                    changed = true;
+                }
+            }
+        }
+        changed
+    }
+
+    /// Scanne toutes les sources enregistrées, dans l'ordre d'enregistrement.
+    pub fn scan_all(&self, settings: &UserData) -> Vec<Game> {
+        self.providers.iter().flat_map(|p| p.scan_with_settings(settings)).collect()
+    }
+}
+
+// ===================== OUTILS COMMUNS =====================
+
+/// Dossier personnel de l'utilisateur ($HOME sous Linux, %USERPROFILE% sous Windows).
+pub fn home_dir() -> Option<PathBuf> {
+    std::env::var_os("HOME")
+        .or_else(|| std::env::var_os("USERPROFILE"))
+        .filter(|h| !h.is_empty())
+        .map(PathBuf::from)
+}
+
+/// Racines des disques présents (C:\, D:\... sous Windows, / ailleurs).
+pub fn system_drives() -> Vec<String> {
+    let mut drives = Vec::new();
+    if cfg!(windows) {
+        for drive in ('A'..='Z').map(|c| format!("{}:\\", c)) {
+            if Path::new(&drive).exists() {
+                drives.push(drive);
+            }
+        }
+    } else {
+        drives.push("/".to_string());
+    }
+    drives
+}
+
+/// Découpe une ligne de commande en arguments (espaces, guillemets simples ou doubles).
+/// Entre guillemets doubles, seuls \", \\, \$ et \` sont des échappements (comme dans l'Exec des .desktop) :
+/// les chemins Windows restent intacts.
+pub fn split_command_line(command: &str) -> Vec<String> {
+    let mut args = Vec::new();
+    let mut current = String::new();
+    let mut in_arg = false;
+    let mut quote: Option<char> = None;
+    let mut chars = command.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        match (quote, c) {
+            (Some(q), c) if c == q => quote = None,
+            (Some('"'), '\\') if matches!(chars.peek(), Some('"' | '\\' | '$' | '`')) => current.extend(chars.next()),
+            (Some(_), c) => current.push(c),
+            (None, '"' | '\'') => {
+                quote = Some(c);
+                in_arg = true;
+            }
+            (None, c) if c.is_whitespace() => {
+                if in_arg {
+                    args.push(std::mem::take(&mut current));
+                    in_arg = false;
+                }
+            }
+            (None, c) => {
+                current.push(c);
+                in_arg = true;
+            }
+        }
+    }
+    if in_arg {
+        args.push(current);
+    }
+    args
+}
+
+/// Ouvre une base SQLite d'un launcher en lecture seule (le launcher peut être ouvert en même temps).
+pub fn open_sqlite_read_only(path: &Path) -> Option<rusqlite::Connection> {
+    use rusqlite::OpenFlags;
+    if !path.is_file() { return None; }
+    rusqlite::Connection::open_with_flags(path, OpenFlags::SQLITE_OPEN_READ_ONLY | OpenFlags::SQLITE_OPEN_NO_MUTEX).ok()
+}
+
+/// Extensions des images trouvées sur disque (jaquettes, bannières...).
+const IMAGE_EXTENSIONS: [&str; 4] = ["jpg", "png", "jpeg", "webp"];
+
+/// Cherche une image (cover, banner, logo...) directement dans le dossier d'installation.
+pub fn scavenge_image(install_dir: &str) -> String {
+    if install_dir.is_empty() { return "".to_string(); }
+    let candidates = ["cover", "banner", "poster", "splash", "header", "logo", "background", "boxart"];
+    scavenge_image_in(Path::new(install_dir), |stem| candidates.iter().any(|c| stem.contains(c))).unwrap_or_default()
+}
+
+/// Première image de `dir` dont le nom sans extension, en minuscules, est accepté par `matches`.
+pub fn scavenge_image_in(dir: &Path, matches: impl Fn(&str) -> bool) -> Option<String> {
+    fs::read_dir(dir).ok()?.flatten().map(|entry| entry.path()).find_map(|p| {
+        if !p.is_file() { return None; }
+        let ext = p.extension()?.to_str()?.to_lowercase();
+        let stem = p.file_stem()?.to_str()?.to_lowercase();
+        if IMAGE_EXTENSIONS.contains(&ext.as_str()) && matches(&stem) {
+            Some(p.to_string_lossy().to_string())
+        } else {
+            None
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn system(name: &str) -> EmulatorSystem {
+        EmulatorSystem { name: name.to_string(), ..Default::default() }
+    }
+
+    #[test]
+    fn rejects_emulator_systems_named_like_a_platform() {
+        let mut registry = ProviderRegistry::new();
+        registry.register(XboxProvider);
+        registry.register(RetroArchProvider);
+        registry.register(EmulatorProvider);
+        registry.register(CustomProvider);
+
+        assert!(registry.check_emulator_systems(&[system("SNES"), system("Xbox 360")]).is_ok());
+        for name in ["Xbox", "retroarch", "Emulator", " Custom "] {
+            assert!(registry.check_emulator_systems(&[system("SNES"), system(name)]).is_err(), "{}", name);
+        }
+
+        let settings = UserData { emulator_systems: vec![system("SNES")], ..Default::default() };
+        assert_eq!(registry.get_for("SNES", &settings).map(|p| p.platform_id()), Some("Emulator"));
+        assert_eq!(registry.get_for("Xbox", &settings).map(|p| p.platform_id()), Some("Xbox"));
+        assert!(registry.get_for("N64", &settings).is_none());
+
+        // Même normalisation que check_emulator_systems : casse et espaces ignorés
+        let settings = UserData { emulator_systems: vec![system(" Super Nintendo ")], ..Default::default() };
+        assert_eq!(registry.get_for("super nintendo", &settings).map(|p| p.platform_id()), Some("Emulator"));
+    }
+
+    #[test]
+    fn splits_command_lines() {
+        assert_eq!(split_command_line("retroarch -L snes9x_libretro.so {rom}"), ["retroarch", "-L", "snes9x_libretro.so", "{rom}"]);
+        assert_eq!(split_command_line("  mednafen\t  -force_module   pce  "), ["mednafen", "-force_module", "pce"]);
+        // Guillemets doubles : chemin Windows intact, seuls \" et \\ sont échappés
+        assert_eq!(
+            split_command_line(r#""C:\Program Files\Dolphin\Dolphin.exe" -e "{rom}""#),
+            [r"C:\Program Files\Dolphin\Dolphin.exe", "-e", "{rom}"]
+        );
+        assert_eq!(split_command_line(r#"echo "dit \"bonjour\" \\ fin""#), ["echo", r#"dit "bonjour" \ fin"#]);
+        // Guillemets simples : aucun échappement, les guillemets doubles sont littéraux
+        assert_eq!(split_command_line(r#"sh -c 'echo "{name}" \n'"#), ["sh", "-c", r#"echo "{name}" \n"#]);
+        // Argument vide entre guillemets conservé, guillemets collés à du texte fusionnés
+        assert_eq!(split_command_line(r#"emu "" --core=' snes'"#), ["emu", "", "--core= snes"]);
+        assert!(split_command_line("").is_empty());
+        assert!(split_command_line(" \t \n ").is_empty());
+    }
+
+    #[test]
+    fn scavenges_images_by_name() {
+        let dir = std::env::temp_dir().join(format!("geewers-scavenge-{}", std::process::id()));
+        fs::remove_dir_all(&dir).ok();
+        fs::create_dir_all(dir.join("cover.png")).unwrap();
+        fs::write(dir.join("readme.txt"), b"").unwrap();
+        fs::write(dir.join("cover.txt"), b"").unwrap();
+        assert_eq!(scavenge_image(&dir.to_string_lossy()), "");
+
+        fs::write(dir.join("Game_Banner.WEBP"), b"").unwrap();
+        assert_eq!(scavenge_image(&dir.to_string_lossy()), dir.join("Game_Banner.WEBP").to_string_lossy());
+        assert_eq!(scavenge_image_in(&dir, |stem| stem == "game_banner"), Some(dir.join("Game_Banner.WEBP").to_string_lossy().to_string()));
+        assert_eq!(scavenge_image_in(&dir, |stem| stem == "readme"), None);
+        assert_eq!(scavenge_image(""), "");
+
+        fs::remove_dir_all(&dir).ok();
+    }
+}
@@ -0,0 +1,381 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use serde_json::Value;
+
+use super::emulator::clean_rom_title;
+use super::{home_dir, GameProvider};
+use crate::models::Game;
+
+// ===================== RETROARCH (PLAYLISTS .lpl) =====================
+// RetroArch range le contenu scanné dans des playlists (playlists/<système>.lpl) :
+// JSON depuis la 1.7 ({ "default_core_path", "items": [ { "path", "label", "core_path", "db_name" } ] }),
+// ou 6 lignes par entrée dans l'ancien format. Les jaquettes sont dans
+// thumbnails/<playlist>/Named_Boxarts/<label>.png (caractères interdits remplacés par "_").
+// L'id d'un jeu est le chemin de la ROM ; au lancement on relit les playlists pour retrouver son core.
+
+/// Une installation de RetroArch et la commande pour la lancer.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct RetroArchInstall {
+    pub playlist_dir: PathBuf,
+    pub thumbnails_dir: PathBuf,
+    pub command: Vec<String>,
+}
+
+/// Une entrée de playlist.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct PlaylistEntry {
+    pub path: String,
+    pub label: String,
+    /// Chemin du core, vide si RetroArch doit le choisir ("DETECT")
+    pub core_path: String,
+    pub db_name: String,
+}
+
+pub struct RetroArchProvider;
+
+impl GameProvider for RetroArchProvider {
+    fn platform_id(&self) -> &str {
+        "RetroArch"
+    }
+
+    fn scan(&self) -> Vec<Game> {
+        let mut games: Vec<Game> = Vec::new();
+        for install in retroarch_installs() {
+            for game in get_retroarch_games(&install) {
+                if !games.iter().any(|g| g.id == game.id) {
+                    games.push(game);
+                }
+            }
+        }
+        games
+    }
+
+    fn launch(&self, id: &str, _exe_path: &str) {
+        for install in retroarch_installs() {
+            let Some((_, entry)) = read_playlists(&install.playlist_dir).into_iter().find(|(_, e)| e.path == id) else { continue };
+            let Some((program, base_args)) = install.command.split_first() else { continue };
+            let mut args = base_args.to_vec();
+            if !entry.core_path.is_empty() {
+                args.push("-L".to_string());
+                args.push(entry.core_path.clone());
+            }
+            args.push(entry.path.clone());
+            if Command::new(program).args(&args).spawn().is_ok() {
+                return;
+            }
+        }
+        opener::open(id).ok();
+    }
+
+    // Ouvre le dossier de la ROM
+    fn open_store_page(&self, id: &str) {
+        if let Some(dir) = Path::new(id).parent() {
+            opener::open(dir).ok();
+        }
+    }
+}
+
+/// Installations de RetroArch trouvées (Linux natif, Flatpak, Windows, macOS).
+pub fn retroarch_installs() -> Vec<RetroArchInstall> {
+    let mut candidates: Vec<(PathBuf, Vec<String>)> = Vec::new();
+    if let Some(appdata) = std::env::var_os("APPDATA").filter(|v| !v.is_empty()) {
+        let dir = PathBuf::from(appdata).join("RetroArch");
+        let exe = dir.join("retroarch.exe");
+        let command = if exe.is_file() { exe.to_string_lossy().to_string() } else { "retroarch".to_string() };
+        candidates.push((dir, vec![command]));
+    }
+    if let Some(xdg) = std::env::var_os("XDG_CONFIG_HOME").filter(|v| !v.is_empty()) {
+        candidates.push((PathBuf::from(xdg).join("retroarch"), vec!["retroarch".to_string()]));
+    }
+    if let Some(home) = home_dir() {
+        candidates.push((home.join(".config/retroarch"), vec!["retroarch".to_string()]));
+        candidates.push((
+            home.join(".var/app/org.libretro.RetroArch/config/retroarch"),
+            vec!["flatpak".to_string(), "run".to_string(), "org.libretro.RetroArch".to_string()],
+        ));
+        candidates.push((
+            home.join("Library/Application Support/RetroArch"),
+            vec!["/Applications/RetroArch.app/Contents/MacOS/RetroArch".to_string()],
+        ));
+    }
+
+    let mut installs: Vec<RetroArchInstall> = Vec::new();
+    for (config_dir, command) in candidates {
+        let cfg = fs::read_to_string(config_dir.join("retroarch.cfg")).unwrap_or_default();
+        let dir_setting = |key: &str, default: &str| {
+            parse_cfg_value(&cfg, key)
+                .filter(|v| !v.is_empty() && v != "default")
+                .map(|v| expand_cfg_path(&v, &config_dir))
+                .unwrap_or_else(|| config_dir.join(default))
+        };
+        let install = RetroArchInstall {
+            playlist_dir: dir_setting("playlist_directory", "playlists"),
+            thumbnails_dir: dir_setting("thumbnails_directory", "thumbnails"),
+            command,
+        };
+        if install.playlist_dir.is_dir() && !installs.iter().any(|i| i.playlist_dir == install.playlist_dir) {
+            installs.push(install);
+        }
+    }
+    installs
+}
+
+/// Valeur d'une clé de retroarch.cfg (lignes `cle = "valeur"`).
+pub fn parse_cfg_value(cfg: &str, key: &str) -> Option<String> {
+    cfg.lines().find_map(|line| {
+        let (k, v) = line.split_once('=')?;
+        if k.trim() != key { return None; }
+        Some(v.trim().trim_matches('"').to_string())
+    })
+}
+
+// "~/..." = dossier personnel, ":/..." = dossier de RetroArch (installations portables Windows)
+fn expand_cfg_path(value: &str, config_dir: &Path) -> PathBuf {
+    if let Some(rest) = value.strip_prefix("~/") {
+        if let Some(home) = home_dir() {
+            return home.join(rest);
+        }
+    }
+    if let Some(rest) = value.strip_prefix(':') {
+        return config_dir.join(rest.trim_start_matches(['/', '\\']));
+    }
+    PathBuf::from(value)
+}
+
+pub fn get_retroarch_games(install: &RetroArchInstall) -> Vec<Game> {
+    read_playlists(&install.playlist_dir)
+        .into_iter()
+        .map(|(playlist, entry)| {
+            let stem = Path::new(&entry.path).file_stem().unwrap_or_default().to_string_lossy().to_string();
+            let title = if entry.label.is_empty() { clean_rom_title(&stem) } else { entry.label.clone() };
+            let image_path = find_thumbnail(&install.thumbnails_dir, &playlist, &entry).unwrap_or_default();
+            let install_dir = Path::new(&entry.path).parent().map(|p| p.to_string_lossy().to_string()).unwrap_or_default();
+            Game {
+                id: entry.path.clone(),
+                title,
+                platform: "RetroArch".to_string(),
+                image_path,
+                exe_path: entry.path,
+                install_dir,
+                ..Default::default()
+            }
+        })
+        .collect()
+}
+
+/// Entrées de toutes les playlists d'un dossier, avec le nom de leur playlist.
+/// L'historique et les favoris ("content_*.lpl") ne font que répéter les autres playlists.
+pub fn read_playlists(playlist_dir: &Path) -> Vec<(String, PlaylistEntry)> {
+    let Ok(entries) = fs::read_dir(playlist_dir) else { return Vec::new() };
+    let mut files: Vec<PathBuf> = entries
+        .flatten()
+        .map(|e| e.path())
+        .filter(|p| p.extension().is_some_and(|e| e.eq_ignore_ascii_case("lpl")))
+        .filter(|p| !p.file_name().unwrap_or_default().to_string_lossy().starts_with("content_"))
+        .collect();
+    files.sort();
+
+    let mut result = Vec::new();
+    for file in files {
+        let name = file.file_stem().unwrap_or_default().to_string_lossy().to_string();
+        let content = fs::read_to_string(&file).unwrap_or_default();
+        result.extend(parse_playlist(&content).into_iter().map(|entry| (name.clone(), entry)));
+    }
+    result
+}
+
+/// Parse une playlist, au format JSON ou à l'ancien format ligne par ligne.
+pub fn parse_playlist(content: &str) -> Vec<PlaylistEntry> {
+    let core = |path: &str| if path.is_empty() || path.eq_ignore_ascii_case("DETECT") { "".to_string() } else { path.to_string() };
+
+    let entries: Vec<PlaylistEntry> = match serde_json::from_str::<Value>(content) {
+        Ok(json) => {
+            let default_core = core(json["default_core_path"].as_str().unwrap_or(""));
+            json["items"]
+                .as_array()
+                .map(|items| {
+                    items
+                        .iter()
+                        .map(|item| {
+                            let text = |key: &str| item[key].as_str().unwrap_or("").to_string();
+                            let core_path = core(&text("core_path"));
+                            PlaylistEntry {
+                                path: text("path"),
+                                label: text("label"),
+                                core_path: if core_path.is_empty() { default_core.clone() } else { core_path },
+                                db_name: text("db_name"),
+                            }
+                        })
+                        .collect()
+                })
+                .unwrap_or_default()
+        }
+        Err(_) => {
+            // Ancien format : path, label, core_path, core_name, crc, db_name
+            let lines: Vec<&str> = content.lines().collect();
+            lines
+                .chunks(6)
+                .filter(|chunk| chunk.len() >= 2)
+                .map(|chunk| PlaylistEntry {
+                    path: chunk[0].trim().to_string(),
+                    label: chunk[1].trim().to_string(),
+                    core_path: core(chunk.get(2).map(|c| c.trim()).unwrap_or("")),
+                    db_name: chunk.get(5).map(|c| c.trim()).unwrap_or("").to_string(),
+                })
+                .collect()
+        }
+    };
+    entries.into_iter().filter(|e| !e.path.is_empty()).collect()
+}
+
+/// Jaquette d'une entrée : Named_Boxarts, puis Named_Titles et Named_Snaps, dans le dossier de sa
+/// playlist ou de sa base (db_name) si la playlist a été renommée.
+pub fn find_thumbnail(thumbnails_dir: &Path, playlist: &str, entry: &PlaylistEntry) -> Option<String> {
+    let label = if entry.label.is_empty() {
+        Path::new(&entry.path).file_stem()?.to_string_lossy().to_string()
+    } else {
+        entry.label.clone()
+    };
+    let file_name = format!("{}.png", thumbnail_name(&label));
+    let db = entry.db_name.trim_end_matches(".lpl");
+
+    [playlist, db].iter().filter(|p| !p.is_empty()).find_map(|system| {
+        ["Named_Boxarts", "Named_Titles", "Named_Snaps"]
+            .iter()
+            .map(|kind| thumbnails_dir.join(system).join(kind).join(&file_name))
+            .find(|p| p.is_file())
+            .map(|p| p.to_string_lossy().to_string())
+    })
+}
+
+// RetroArch remplace les caractères interdits dans les noms de fichiers par "_"
+fn thumbnail_name(label: &str) -> String {
+    label.chars().map(|c| if "&*/:`\"<>?\\|".contains(c) { '_' } else { c }).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Playlist JSON telle qu'écrite par RetroArch 1.7+
+    const JSON_PLAYLIST: &str = r#"{
+  "version": "1.5",
+  "default_core_path": "/usr/lib/libretro/snes9x_libretro.so",
+  "default_core_name": "Nintendo - SNES / SFC (Snes9x - Current)",
+  "label_display_mode": 0,
+  "items": [
+    {
+      "path": "/roms/snes/Zelda (USA).sfc",
+      "label": "Legend of Zelda, The - A Link to the Past (USA)",
+      "core_path": "DETECT",
+      "core_name": "DETECT",
+      "crc32": "777AAC2F|crc",
+      "db_name": "Nintendo - Super Nintendo Entertainment System.lpl"
+    },
+    {
+      "path": "/roms/snes/Mario.sfc",
+      "label": "Super Mario World",
+      "core_path": "/usr/lib/libretro/bsnes_libretro.so",
+      "core_name": "bsnes",
+      "db_name": "Nintendo - Super Nintendo Entertainment System.lpl"
+    },
+    { "path": "", "label": "Sans chemin" }
+  ]
+}"#;
+
+    // Ancien format : 6 lignes par entrée
+    const LEGACY_PLAYLIST: &str = "/roms/gba/Metroid Fusion (USA).gba\nMetroid Fusion (USA)\nDETECT\nDETECT\n5DE8B3F7|crc\nNintendo - Game Boy Advance.lpl\n/roms/gba/Zero Mission.gba\nMetroid - Zero Mission (USA)\n/usr/lib/libretro/mgba_libretro.so\nmGBA\n00000000|crc\nNintendo - Game Boy Advance.lpl\n";
+
+    #[test]
+    fn parses_json_playlists() {
+        let entries = parse_playlist(JSON_PLAYLIST);
+        assert_eq!(entries.len(), 2);
+        // "DETECT" : le core par défaut de la playlist
+        assert_eq!(entries[0], PlaylistEntry {
+            path: "/roms/snes/Zelda (USA).sfc".to_string(),
+            label: "Legend of Zelda, The - A Link to the Past (USA)".to_string(),
+            core_path: "/usr/lib/libretro/snes9x_libretro.so".to_string(),
+            db_name: "Nintendo - Super Nintendo Entertainment System.lpl".to_string(),
+        });
+        assert_eq!(entries[1].core_path, "/usr/lib/libretro/bsnes_libretro.so");
+
+        // Core par défaut lui-même à "DETECT" : RetroArch choisira
+        let detect = JSON_PLAYLIST.replace("/usr/lib/libretro/snes9x_libretro.so", "DETECT");
+        assert_eq!(parse_playlist(&detect)[0].core_path, "");
+    }
+
+    #[test]
+    fn parses_legacy_playlists() {
+        let entries = parse_playlist(LEGACY_PLAYLIST);
+        assert_eq!(entries.len(), 2);
+        assert_eq!(entries[0].path, "/roms/gba/Metroid Fusion (USA).gba");
+        assert_eq!(entries[0].label, "Metroid Fusion (USA)");
+        assert_eq!(entries[0].core_path, "");
+        assert_eq!(entries[0].db_name, "Nintendo - Game Boy Advance.lpl");
+        assert_eq!(entries[1].core_path, "/usr/lib/libretro/mgba_libretro.so");
+
+        // Entrée tronquée : le chemin seul ne suffit pas
+        assert!(parse_playlist("/roms/gba/Seul.gba\n").is_empty());
+        assert!(parse_playlist("").is_empty());
+    }
+
+    #[test]
+    fn reads_quoted_cfg_values() {
+        let cfg = "menu_driver = \"ozone\"\nplaylist_directory = \"~/Jeux/playlists\"\nthumbnails_directory=\":\\thumbnails\"\nvideo_fullscreen = false\nplaylist_directory_extra = \"autre\"\n";
+        assert_eq!(parse_cfg_value(cfg, "playlist_directory"), Some("~/Jeux/playlists".to_string()));
+        assert_eq!(parse_cfg_value(cfg, "thumbnails_directory"), Some(":\\thumbnails".to_string()));
+        assert_eq!(parse_cfg_value(cfg, "video_fullscreen"), Some("false".to_string()));
+        assert_eq!(parse_cfg_value(cfg, "savefile_directory"), None);
+
+        let config_dir = Path::new("/portable/RetroArch");
+        assert_eq!(expand_cfg_path(":\\thumbnails", config_dir), config_dir.join("thumbnails"));
+        assert_eq!(expand_cfg_path("/srv/playlists", config_dir), PathBuf::from("/srv/playlists"));
+    }
+
+    #[test]
+    fn finds_sanitized_thumbnails() {
+        let root = std::env::temp_dir().join(format!("geewers-retroarch-{}", std::process::id()));
+        fs::remove_dir_all(&root).ok();
+        let thumbnails = root.join("thumbnails");
+        let playlists = root.join("playlists");
+        let boxarts = thumbnails.join("Nintendo - Game Boy Advance").join("Named_Boxarts");
+        let titles = thumbnails.join("Mes GBA").join("Named_Titles");
+        fs::create_dir_all(&boxarts).unwrap();
+        fs::create_dir_all(&titles).unwrap();
+        fs::create_dir_all(&playlists).unwrap();
+
+        assert_eq!(thumbnail_name("Q*bert: Rebooted & <Deluxe>? \"A/B\" `1\\2|3`"), "Q_bert_ Rebooted _ _Deluxe__ _A_B_ _1_2_3_");
+
+        // Playlist renommée : la jaquette est trouvée par le db_name
+        let entry = PlaylistEntry {
+            path: "/roms/gba/Metroid Fusion (USA).gba".to_string(),
+            label: "Metroid: Fusion (USA)".to_string(),
+            core_path: "".to_string(),
+            db_name: "Nintendo - Game Boy Advance.lpl".to_string(),
+        };
+        fs::write(boxarts.join("Metroid_ Fusion (USA).png"), b"").unwrap();
+        assert_eq!(
+            find_thumbnail(&thumbnails, "Mes GBA", &entry),
+            Some(boxarts.join("Metroid_ Fusion (USA).png").to_string_lossy().to_string())
+        );
+        // Le dossier de la playlist passe avant celui de la base
+        fs::write(titles.join("Metroid_ Fusion (USA).png"), b"").unwrap();
+        assert_eq!(
+            find_thumbnail(&thumbnails, "Mes GBA", &entry),
+            Some(titles.join("Metroid_ Fusion (USA).png").to_string_lossy().to_string())
+        );
+
+        // Scan complet : l'historique est ignoré, le titre vient du label
+        fs::write(playlists.join("Mes GBA.lpl"), LEGACY_PLAYLIST.replace("Metroid Fusion (USA)\n", "Metroid: Fusion (USA)\n")).unwrap();
+        fs::write(playlists.join("content_history.lpl"), JSON_PLAYLIST).unwrap();
+        let install = RetroArchInstall { playlist_dir: playlists, thumbnails_dir: thumbnails, command: vec!["retroarch".to_string()] };
+        let games = get_retroarch_games(&install);
+        let titles: Vec<&str> = games.iter().map(|g| g.title.as_str()).collect();
+        assert_eq!(titles, ["Metroid: Fusion (USA)", "Metroid - Zero Mission (USA)"]);
+        assert!(!games[0].image_path.is_empty());
+        assert_eq!(games[1].image_path, "");
+
+        fs::remove_dir_all(&root).ok();
+    }
+}
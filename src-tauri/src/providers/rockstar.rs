@@ -0,0 +1,172 @@
+use std::path::Path;
+use std::process::Command;
+use std::sync::Arc;
+
+use super::{scavenge_image, GameProvider};
+use crate::models::Game;
+use crate::registry::{Hive, RegistryReader};
+
+// ===================== ROCKSTAR GAMES LAUNCHER =====================
+// Chaque jeu a sa clé HKLM\SOFTWARE\WOW6432Node\Rockstar Games\<titre> avec une valeur InstallFolder.
+// Le launcher lui-même est sous la clé "Launcher" et sait démarrer un jeu à partir de son dossier :
+//   Launcher.exe -launchTitleInFolder "<InstallFolder>"
+
+const ROCKSTAR_KEY: &str = "SOFTWARE\\WOW6432Node\\Rockstar Games";
+
+// Clés qui ne sont pas des jeux
+const IGNORED_KEYS: [&str; 3] = ["Launcher", "Rockstar Games Social Club", "Social Club"];
+
+// Nom de clé -> (titre affiché, id du titre dans le launcher, exécutable relatif au dossier)
+const KNOWN_TITLES: [(&str, &str, &str, &str); 14] = [
+    ("Grand Theft Auto V", "Grand Theft Auto V", "gta5", "PlayGTAV.exe"),
+    ("GTAV Enhanced", "Grand Theft Auto V Enhanced", "gta5_gen9", "PlayGTAV.exe"),
+    ("Red Dead Redemption 2", "Red Dead Redemption 2", "rdr2", "RDR2.exe"),
+    ("Red Dead Redemption", "Red Dead Redemption", "rdr1", "RDR.exe"),
+    ("L.A. Noire", "L.A. Noire", "lanoire", "LANoire.exe"),
+    ("L.A. Noire: The VR Case Files", "L.A. Noire: The VR Case Files", "lanoirevr", "LANoireVR.exe"),
+    ("Max Payne 3", "Max Payne 3", "mp3", "MaxPayne3.exe"),
+    ("Bully Scholarship Edition", "Bully: Scholarship Edition", "bully", "Bully.exe"),
+    ("Grand Theft Auto IV", "Grand Theft Auto IV: The Complete Edition", "gta4", "GTAIV.exe"),
+    ("GTA San Andreas", "Grand Theft Auto: San Andreas", "gtasa", "gta_sa.exe"),
+    ("GTA III - Definitive Edition", "Grand Theft Auto III – The Definitive Edition", "gta3unreal", "Gameface\\Binaries\\Win64\\LibertyCity.exe"),
+    ("GTA Vice City - Definitive Edition", "Grand Theft Auto: Vice City – The Definitive Edition", "gtavcunreal", "Gameface\\Binaries\\Win64\\ViceCity.exe"),
+    ("GTA San Andreas - Definitive Edition", "Grand Theft Auto: San Andreas – The Definitive Edition", "gtasaunreal", "Gameface\\Binaries\\Win64\\SanAndreas.exe"),
+    ("Grand Theft Auto: The Trilogy - The Definitive Edition", "Grand Theft Auto: The Trilogy – The Definitive Edition", "gtatrilogy", ""),
+];
+
+/// Entrée de KNOWN_TITLES correspondant à un nom de clé (insensible à la casse).
+fn known_title(key_name: &str) -> Option<&'static (&'static str, &'static str, &'static str, &'static str)> {
+    KNOWN_TITLES.iter().find(|(key, _, _, _)| key.eq_ignore_ascii_case(key_name))
+}
+
+pub struct RockstarProvider {
+    registry: Arc<dyn RegistryReader>,
+}
+
+impl RockstarProvider {
+    pub fn new(registry: Arc<dyn RegistryReader>) -> Self {
+        Self { registry }
+    }
+
+    fn install_folder(&self, key_name: &str) -> Option<String> {
+        self.registry
+            .value(Hive::LocalMachine, &format!("{}\\{}", ROCKSTAR_KEY, key_name), "InstallFolder")
+            .filter(|f| !f.is_empty())
+    }
+}
+
+impl GameProvider for RockstarProvider {
+    fn platform_id(&self) -> &str {
+        "Rockstar"
+    }
+
+    fn scan(&self) -> Vec<Game> {
+        let mut games = Vec::new();
+        for key_name in self.registry.subkeys(Hive::LocalMachine, ROCKSTAR_KEY) {
+            if IGNORED_KEYS.iter().any(|k| k.eq_ignore_ascii_case(&key_name)) { continue; }
+            let Some(install_dir) = self.install_folder(&key_name) else { continue };
+            if !Path::new(&install_dir).exists() { continue; }
+
+            let known = known_title(&key_name);
+            let title = known.map(|(_, t, _, _)| t.to_string()).unwrap_or_else(|| key_name.clone());
+            let exe_path = known
+                .map(|(_, _, _, exe)| *exe)
+                .filter(|exe| !exe.is_empty())
+                .map(|exe| Path::new(&install_dir).join(exe).to_string_lossy().to_string())
+                .unwrap_or_default();
+
+            games.push(Game {
+                // L'id est le nom de la clé : c'est lui qui permet de retrouver le dossier au lancement
+                id: key_name,
+                title,
+                platform: "Rockstar".to_string(),
+                image_path: scavenge_image(&install_dir),
+                exe_path,
+                install_dir,
+                ..Default::default()
+            });
+        }
+        games
+    }
+
+    // Le launcher gère la connexion au Social Club et les mises à jour ;
+    // s'il est introuvable, on lance directement l'exécutable du jeu.
+    fn launch(&self, id: &str, exe_path: &str) {
+        let launcher = self.install_folder("Launcher").map(|dir| Path::new(&dir).join("Launcher.exe"));
+        if let (Some(launcher), Some(folder)) = (launcher, self.install_folder(id)) {
+            if launcher.is_file() && Command::new(&launcher).arg("-launchTitleInFolder").arg(&folder).spawn().is_ok() {
+                return;
+            }
+        }
+        if !exe_path.is_empty() {
+            opener::open(exe_path).ok();
+        }
+    }
+
+    fn open_store_page(&self, id: &str) {
+        match known_title(id).map(|(_, _, title_id, _)| *title_id) {
+            Some(title_id) => opener::open(format!("rockstar://v1/viewtitle/{}", title_id)).ok(),
+            None => opener::open("https://store.rockstargames.com/").ok(),
+        };
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::registry::MemoryRegistry;
+    use std::fs;
+
+    #[test]
+    fn scans_game_keys_only() {
+        let root = std::env::temp_dir().join(format!("geewers-rockstar-{}", std::process::id()));
+        fs::remove_dir_all(&root).ok();
+        let folder = |name: &str| {
+            let dir = root.join(name);
+            fs::create_dir_all(&dir).unwrap();
+            dir.to_string_lossy().to_string()
+        };
+
+        let mut registry = MemoryRegistry::new();
+        let mut game_key = |key: &str, name: &str, value: &str| {
+            registry.insert(Hive::LocalMachine, &format!("{}\\{}", ROCKSTAR_KEY, key), name, value);
+        };
+        game_key("Launcher", "InstallFolder", &folder("Launcher"));
+        game_key("Rockstar Games Social Club", "InstallFolder", &folder("Social Club"));
+        game_key("grand theft auto v", "InstallFolder", &folder("GTAV"));
+        game_key("Grand Theft Auto: The Trilogy - The Definitive Edition", "InstallFolder", &folder("Trilogy"));
+        game_key("Nouveau Jeu", "InstallFolder", &folder("Nouveau Jeu"));
+        // Dossier vide, absent du disque ou non renseigné : pas de jeu
+        game_key("Max Payne 3", "InstallFolder", "");
+        game_key("Red Dead Redemption 2", "InstallFolder", &root.join("absent").to_string_lossy());
+        game_key("L.A. Noire", "Language", "fr-FR");
+
+        let mut games = RockstarProvider::new(Arc::new(registry)).scan();
+        games.sort_by(|a, b| a.id.cmp(&b.id));
+        let ids: Vec<&str> = games.iter().map(|g| g.id.as_str()).collect();
+        assert_eq!(ids, ["Grand Theft Auto: The Trilogy - The Definitive Edition", "Nouveau Jeu", "grand theft auto v"]);
+
+        // Titre connu : nom affiché et exécutable ; la trilogie n'a pas d'exécutable propre
+        assert_eq!(games[2].title, "Grand Theft Auto V");
+        assert_eq!(games[2].exe_path, Path::new(&folder("GTAV")).join("PlayGTAV.exe").to_string_lossy());
+        assert_eq!(games[2].platform, "Rockstar");
+        assert_eq!(games[0].title, "Grand Theft Auto: The Trilogy – The Definitive Edition");
+        assert_eq!(games[0].exe_path, "");
+        // Titre inconnu : le nom de la clé
+        assert_eq!(games[1].title, "Nouveau Jeu");
+        assert_eq!(games[1].exe_path, "");
+        assert_eq!(games[1].install_dir, folder("Nouveau Jeu"));
+
+        fs::remove_dir_all(&root).ok();
+    }
+
+    #[test]
+    fn maps_key_names_to_launcher_title_ids() {
+        let title_id = |key: &str| known_title(key).map(|(_, _, title_id, _)| *title_id);
+        assert_eq!(title_id("Grand Theft Auto V"), Some("gta5"));
+        assert_eq!(title_id("red dead redemption 2"), Some("rdr2"));
+        assert_eq!(title_id("Red Dead Redemption"), Some("rdr1"));
+        assert_eq!(title_id("GTA San Andreas - Definitive Edition"), Some("gtasaunreal"));
+        assert_eq!(title_id("Nouveau Jeu"), None);
+    }
+}
@@ -0,0 +1,345 @@
+use std::collections::{HashMap, HashSet};
+use std::fs;
+use std::path::Path;
+
+use crate::vdf::{BinaryReader, KeyValues};
+
+// ===================== appcache/appinfo.vdf =====================
+// Cache binaire des métadonnées de toutes les apps connues du client Steam.
+//
+// En-tête : magic (u32) + univers (u32) [+ offset de la table de chaînes (i64) en v29]
+// Puis pour chaque app : appid (u32, 0 = fin), taille (u32), état, date de mise à jour,
+// token PICS, SHA1, numéro de changement [+ SHA1 binaire en v28+], et enfin les KeyValues binaires.
+
+const MAGIC_V27: u32 = 0x0756_4427;
+const MAGIC_V28: u32 = 0x0756_4428;
+const MAGIC_V29: u32 = 0x0756_4429;
+
+/// Une entrée "launch" de la config de l'app (exécutable réel, arguments, OS ciblés...).
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct LaunchEntry {
+    pub executable: String,
+    pub arguments: String,
+    pub working_dir: String,
+    pub description: String,
+    /// "default", "option1", "server", "editor"... vide si non précisé.
+    pub launch_type: String,
+    /// OS ciblés ("windows", "linux", "macos"), vide = tous.
+    pub oslist: Vec<String>,
+}
+
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct AppInfo {
+    pub appid: u32,
+    pub name: String,
+    /// Type en minuscules : "game", "tool", "config", "application", "demo", "dlc"...
+    pub app_type: String,
+    pub developer: String,
+    pub publisher: String,
+    /// Date de sortie, timestamp Unix (0 si inconnue).
+    pub release_date: u64,
+    pub launch: Vec<LaunchEntry>,
+}
+
+impl AppInfo {
+    pub fn from_keyvalues(appid: u32, root: &KeyValues) -> Self {
+        // Selon la version, les données sont directement à la racine ou sous "appinfo"
+        let app = root.get_obj("appinfo").unwrap_or(root);
+        let common = app.get_obj("common");
+        let extended = app.get_obj("extended");
+        let common_str = |key: &str| common.and_then(|c| c.get_str(key)).unwrap_or_default().to_string();
+        let extended_str = |key: &str| extended.and_then(|e| e.get_str(key)).unwrap_or_default().to_string();
+
+        let developer = Some(extended_str("developer"))
+            .filter(|d| !d.is_empty())
+            .or_else(|| association(common, "developer"))
+            .unwrap_or_default();
+        let publisher = Some(extended_str("publisher"))
+            .filter(|p| !p.is_empty())
+            .or_else(|| association(common, "publisher"))
+            .unwrap_or_default();
+
+        let release_date = common
+            .and_then(|c| c.get_u64("steam_release_date").or_else(|| c.get_u64("original_release_date")))
+            .unwrap_or(0);
+
+        let mut launch = Vec::new();
+        if let Some(entries) = app.get_obj("config").and_then(|c| c.get_obj("launch")) {
+            for (_, entry) in entries.iter() {
+                let Some(entry) = entry.as_obj() else { continue };
+                let text = |key: &str| entry.get_str(key).unwrap_or_default().to_string();
+                let oslist = entry
+                    .get_obj("config")
+                    .and_then(|c| c.get_str("oslist"))
+                    .map(|list| list.split(',').map(|os| os.trim().to_lowercase()).filter(|os| !os.is_empty()).collect())
+                    .unwrap_or_default();
+                launch.push(LaunchEntry {
+                    executable: text("executable"),
+                    arguments: text("arguments"),
+                    working_dir: text("workingdir"),
+                    description: text("description"),
+                    launch_type: text("type").to_lowercase(),
+                    oslist,
+                });
+            }
+        }
+
+        Self {
+            appid,
+            name: common_str("name"),
+            app_type: common_str("type").to_lowercase(),
+            developer,
+            publisher,
+            release_date,
+            launch,
+        }
+    }
+
+    /// Entrée de lancement principale pour l'OS courant. À défaut (ex: jeu Windows lancé via Proton),
+    /// la première entrée par défaut quel que soit l'OS.
+    pub fn primary_launch(&self) -> Option<&LaunchEntry> {
+        let os = current_os();
+        let is_default = |e: &&LaunchEntry| e.launch_type.is_empty() || e.launch_type == "default" || e.launch_type == "none";
+        let for_os = |e: &&LaunchEntry| e.oslist.is_empty() || e.oslist.iter().any(|o| o == os);
+
+        self.launch.iter().filter(|e| !e.executable.is_empty()).find(|e| is_default(e) && for_os(e))
+            .or_else(|| self.launch.iter().filter(|e| !e.executable.is_empty()).find(for_os))
+            .or_else(|| self.launch.iter().find(|e| !e.executable.is_empty()))
+    }
+}
+
+// "associations" { "0" { "type" "developer" "name" "Valve" } }
+fn association(common: Option<&KeyValues>, kind: &str) -> Option<String> {
+    let list = common?.get_obj("associations")?;
+    let names: Vec<&str> = list
+        .iter()
+        .filter_map(|(_, a)| a.as_obj())
+        .filter(|a| a.get_str("type").is_some_and(|t| t.eq_ignore_ascii_case(kind)))
+        .filter_map(|a| a.get_str("name"))
+        .collect();
+    if names.is_empty() { None } else { Some(names.join(", ")) }
+}
+
+fn current_os() -> &'static str {
+    if cfg!(windows) {
+        "windows"
+    } else if cfg!(target_os = "macos") {
+        "macos"
+    } else {
+        "linux"
+    }
+}
+
+/// Parse appinfo.vdf. Si `wanted` est fourni, seules ces apps sont décodées (les autres
+/// sont sautées grâce à leur taille), ce qui évite de parcourir des dizaines de milliers d'entrées.
+pub fn parse_appinfo(data: &[u8], wanted: Option<&HashSet<u32>>) -> Result<HashMap<u32, AppInfo>, String> {
+    let mut header = BinaryReader::new(data, None);
+    let magic = u32::from_le_bytes(header.take_array()?);
+    let _universe = u32::from_le_bytes(header.take_array()?);
+
+    let key_table = match magic {
+        MAGIC_V27 | MAGIC_V28 => None,
+        MAGIC_V29 => {
+            let offset = i64::from_le_bytes(header.take_array()?);
+            Some(read_string_table(data, offset)?)
+        }
+        other => return Err(format!("appinfo.vdf: version inconnue 0x{:08x}", other)),
+    };
+    let mut pos = header.pos;
+
+    let mut apps = HashMap::new();
+    loop {
+        let mut reader = BinaryReader::new(data, key_table.as_deref());
+        reader.pos = pos;
+        let appid = u32::from_le_bytes(reader.take_array()?);
+        if appid == 0 { break; }
+        let size = u32::from_le_bytes(reader.take_array()?) as usize;
+        let entry_end = reader.pos + size;
+        if entry_end > data.len() {
+            return Err(format!("appinfo.vdf: entrée {} tronquée", appid));
+        }
+
+        if wanted.is_none_or(|w| w.contains(&appid)) {
+            // Lecture limitée à l'entrée ; une entrée illisible est sautée sans perdre les autres apps
+            let mut entry = BinaryReader::new(&data[..entry_end], key_table.as_deref());
+            entry.pos = reader.pos;
+            if let Ok(root) = read_entry(&mut entry, magic) {
+                apps.insert(appid, AppInfo::from_keyvalues(appid, &root));
+            }
+        }
+        pos = entry_end;
+    }
+    Ok(apps)
+}
+
+fn read_entry(reader: &mut BinaryReader, magic: u32) -> Result<KeyValues, String> {
+    // état (4) + dernière MAJ (4) + token PICS (8) + SHA1 (20) + numéro de changement (4)
+    reader.take(40)?;
+    if magic != MAGIC_V27 {
+        reader.take(20)?; // SHA1 des données binaires
+    }
+    reader.read_object()
+}
+
+fn read_string_table(data: &[u8], offset: i64) -> Result<Vec<String>, String> {
+    let offset = usize::try_from(offset).map_err(|_| "appinfo.vdf: offset de table invalide".to_string())?;
+    let mut reader = BinaryReader::new(data, None);
+    reader.pos = offset;
+    let count = u32::from_le_bytes(reader.take_array()?) as usize;
+    let mut table = Vec::with_capacity(count.min(1 << 20));
+    for _ in 0..count {
+        table.push(reader.read_cstring()?);
+    }
+    Ok(table)
+}
+
+/// Métadonnées hors-ligne des apps demandées, lues dans `steam_root/appcache/appinfo.vdf`.
+pub fn read_appinfo(steam_root: &Path, wanted: &HashSet<u32>) -> HashMap<u32, AppInfo> {
+    fs::read(steam_root.join("appcache").join("appinfo.vdf"))
+        .ok()
+        .and_then(|data| parse_appinfo(&data, Some(wanted)).ok())
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // KeyValues binaires "appinfo" { "common" { "name" ... "type" ... } } ; `key` écrit un nom
+    // de clé (texte en v28, index dans la table de chaînes en v29)
+    fn app_keyvalues(name: &str, key: &dyn Fn(&mut Vec<u8>, &str)) -> Vec<u8> {
+        let mut kv = vec![0x00];
+        key(&mut kv, "appinfo");
+        kv.push(0x00);
+        key(&mut kv, "common");
+        for (k, v) in [("name", name), ("type", "Game")] {
+            kv.push(0x01);
+            key(&mut kv, k);
+            kv.extend(v.as_bytes());
+            kv.push(0);
+        }
+        kv.extend([0x08, 0x08, 0x08]);
+        kv
+    }
+
+    fn entry(data: &mut Vec<u8>, appid: u32, kv: &[u8]) {
+        data.extend(appid.to_le_bytes());
+        data.extend(((60 + kv.len()) as u32).to_le_bytes());
+        data.extend([0u8; 60]);
+        data.extend(kv);
+    }
+
+    fn v28_key(kv: &mut Vec<u8>, key: &str) {
+        kv.extend(key.as_bytes());
+        kv.push(0);
+    }
+
+    const V29_TABLE: [&str; 4] = ["appinfo", "common", "name", "type"];
+
+    fn v29_key(kv: &mut Vec<u8>, key: &str) {
+        let index = V29_TABLE.iter().position(|k| *k == key).unwrap() as u32;
+        kv.extend(index.to_le_bytes());
+    }
+
+    fn v28() -> Vec<u8> {
+        let mut data = MAGIC_V28.to_le_bytes().to_vec();
+        data.extend(1u32.to_le_bytes());
+        entry(&mut data, 620, &app_keyvalues("Portal 2", &v28_key));
+        entry(&mut data, 730, &app_keyvalues("Counter-Strike 2", &v28_key));
+        data.extend(0u32.to_le_bytes());
+        // Données après le terminateur : jamais lues
+        data.extend([0xFF; 8]);
+        data
+    }
+
+    fn v29(table: &[&str]) -> Vec<u8> {
+        let mut data = MAGIC_V29.to_le_bytes().to_vec();
+        data.extend(1u32.to_le_bytes());
+        data.extend([0u8; 8]);
+        entry(&mut data, 620, &app_keyvalues("Portal 2", &v29_key));
+        data.extend(0u32.to_le_bytes());
+        let offset = data.len() as i64;
+        data[8..16].copy_from_slice(&offset.to_le_bytes());
+        data.extend((table.len() as u32).to_le_bytes());
+        for key in table {
+            data.extend(key.as_bytes());
+            data.push(0);
+        }
+        data
+    }
+
+    #[test]
+    fn parses_v28_until_the_terminator() {
+        let apps = parse_appinfo(&v28(), None).unwrap();
+        assert_eq!(apps.len(), 2);
+        assert_eq!(apps[&620].name, "Portal 2");
+        assert_eq!(apps[&620].app_type, "game");
+        assert_eq!(apps[&730].name, "Counter-Strike 2");
+    }
+
+    #[test]
+    fn decodes_only_wanted_apps() {
+        let wanted = HashSet::from([730]);
+        let apps = parse_appinfo(&v28(), Some(&wanted)).unwrap();
+        assert_eq!(apps.keys().collect::<Vec<_>>(), vec![&730]);
+    }
+
+    #[test]
+    fn parses_v29_with_its_string_table() {
+        let apps = parse_appinfo(&v29(&V29_TABLE), None).unwrap();
+        assert_eq!(apps[&620].name, "Portal 2");
+        assert_eq!(apps[&620].app_type, "game");
+    }
+
+    #[test]
+    fn skips_entries_with_out_of_range_key_indexes() {
+        assert!(parse_appinfo(&v29(&V29_TABLE[..2]), None).unwrap().is_empty());
+        // Entrée non demandée : sautée sans décoder ses clés
+        let wanted = HashSet::from([730]);
+        assert!(parse_appinfo(&v29(&V29_TABLE[..2]), Some(&wanted)).unwrap().is_empty());
+    }
+
+    #[test]
+    fn keeps_other_apps_when_one_entry_is_malformed() {
+        let mut data = MAGIC_V28.to_le_bytes().to_vec();
+        data.extend(1u32.to_le_bytes());
+        // Type de valeur inconnu (0x09) dans la première entrée
+        entry(&mut data, 10, b"\x09cl\xe9\0\x08");
+        // Entrée trop courte pour son en-tête
+        data.extend(20u32.to_le_bytes());
+        data.extend(4u32.to_le_bytes());
+        data.extend([0u8; 4]);
+        entry(&mut data, 620, &app_keyvalues("Portal 2", &v28_key));
+        data.extend(0u32.to_le_bytes());
+
+        let apps = parse_appinfo(&data, None).unwrap();
+        assert_eq!(apps.keys().collect::<Vec<_>>(), vec![&620]);
+        assert_eq!(apps[&620].name, "Portal 2");
+    }
+
+    #[test]
+    fn reads_developer_publisher_and_release_date() {
+        let mut kv = b"\x00appinfo\0\x00common\0".to_vec();
+        kv.extend(b"\x01name\0Portal 2\0\x02steam_release_date\0");
+        kv.extend(1_303_171_200u32.to_le_bytes());
+        kv.extend(b"\x00associations\0\x000\0\x01type\0publisher\0\x01name\0Valve\0\x08\x08\x08");
+        kv.extend(b"\x00extended\0\x01developer\0Valve\0\x08\x08\x08");
+        let mut data = MAGIC_V28.to_le_bytes().to_vec();
+        data.extend(1u32.to_le_bytes());
+        entry(&mut data, 620, &kv);
+        data.extend(0u32.to_le_bytes());
+
+        let info = &parse_appinfo(&data, None).unwrap()[&620];
+        assert_eq!(info.developer, "Valve");
+        assert_eq!(info.publisher, "Valve");
+        assert_eq!(info.release_date, 1_303_171_200);
+    }
+
+    #[test]
+    fn rejects_truncated_and_unknown_files() {
+        let data = v28();
+        assert!(parse_appinfo(&data[..data.len() - 20], None).is_err());
+        assert!(parse_appinfo(&data[..30], None).is_err());
+        assert!(parse_appinfo(b"\x29\x44\x56\x08\x01\x00\x00\x00", None).is_err());
+    }
+}
@@ -0,0 +1,102 @@
+use super::{AppInfo, AppManifest};
+
+// ===================== OUTILS / RUNTIMES / REDISTRIBUABLES =====================
+// Steam installe des apps qui ne sont pas des jeux (Proton, runtimes Linux, redistribuables...).
+// On les repère d'abord par leur appid, puis par le type du cache appinfo, puis par leur nom.
+
+/// Apps techniques connues, indépendamment de ce que disent les manifestes.
+const KNOWN_TOOL_APPIDS: &[u32] = &[
+    228980,  // Steamworks Common Redistributables
+    1007,    // Steamworks SDK Redist
+    1070560, // Steam Linux Runtime 1.0 (scout)
+    1391110, // Steam Linux Runtime 2.0 (soldier)
+    1628350, // Steam Linux Runtime 3.0 (sniper)
+    858280,  // Proton 3.7
+    930400,  // Proton 3.7 Beta
+    961940,  // Proton 3.16
+    996510,  // Proton 3.16 Beta
+    1054830, // Proton 4.2
+    1113280, // Proton 4.11
+    1245040, // Proton 5.0
+    1420170, // Proton 5.13
+    1580130, // Proton 6.3
+    1887720, // Proton 7.0
+    2348590, // Proton 8.0
+    2805730, // Proton 9.0
+    3658110, // Proton 10.0
+    1493710, // Proton Experimental
+    2180100, // Proton Hotfix
+    1161040, // Proton BattlEye Runtime
+    1826330, // Proton EasyAntiCheat Runtime
+    250820,  // SteamVR
+];
+
+/// Types appinfo qui ne correspondent pas à un jeu jouable.
+/// Les DLC n'y figurent pas : Steam les installe dans le dossier du jeu, sans manifeste propre.
+const NON_GAME_TYPES: &[&str] = &["tool", "config", "music", "video", "series", "comic"];
+
+const NON_GAME_NAME_PREFIXES: &[&str] = &["proton ", "steam linux runtime", "steamworks", "steamvr"];
+const NON_GAME_NAME_PARTS: &[&str] = &["redistributable", " redist", "dedicated server", " sdk"];
+
+/// `true` si l'app est un outil, un runtime ou un redistribuable plutôt qu'un jeu.
+pub fn is_steam_tool(manifest: &AppManifest, info: Option<&AppInfo>) -> bool {
+    if KNOWN_TOOL_APPIDS.contains(&manifest.appid) {
+        return true;
+    }
+    if let Some(info) = info.filter(|i| !i.app_type.is_empty()) {
+        return NON_GAME_TYPES.contains(&info.app_type.as_str());
+    }
+
+    // Pas de cache appinfo : on se rabat sur le nom
+    let name = manifest.name.to_lowercase();
+    NON_GAME_NAME_PREFIXES.iter().any(|p| name.starts_with(p))
+        || NON_GAME_NAME_PARTS.iter().any(|p| name.contains(p))
+        || manifest.installdir.starts_with("SteamLinuxRuntime")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn manifest(appid: u32, name: &str, installdir: &str) -> AppManifest {
+        AppManifest { appid, name: name.to_string(), installdir: installdir.to_string(), ..Default::default() }
+    }
+
+    fn info(app_type: &str) -> AppInfo {
+        AppInfo { app_type: app_type.to_string(), ..Default::default() }
+    }
+
+    #[test]
+    fn trusts_the_appinfo_type() {
+        assert!(is_steam_tool(&manifest(1, "Some Tool", "SomeTool"), Some(&info("tool"))));
+        assert!(is_steam_tool(&manifest(2, "Some Config", "SomeConfig"), Some(&info("config"))));
+        assert!(!is_steam_tool(&manifest(620, "Portal 2", "Portal 2"), Some(&info("game"))));
+        assert!(!is_steam_tool(&manifest(3, "Some Demo", "SomeDemo"), Some(&info("demo"))));
+        assert!(!is_steam_tool(&manifest(4, "Some DLC", "SomeDLC"), Some(&info("dlc"))));
+        // Le type l'emporte sur le nom : un jeu dont le nom contient "SDK" reste un jeu
+        assert!(!is_steam_tool(&manifest(5, "Game SDK Edition", "GameSDK"), Some(&info("game"))));
+    }
+
+    #[test]
+    fn known_appids_win_over_the_appinfo_type() {
+        for (appid, name, installdir) in [
+            (1493710, "Proton Experimental", "Proton - Experimental"),
+            (228980, "Steamworks Common Redistributables", "Steamworks Shared"),
+            (1628350, "Steam Linux Runtime 3.0 (sniper)", "SteamLinuxRuntime_sniper"),
+        ] {
+            assert!(is_steam_tool(&manifest(appid, name, installdir), None), "{}", name);
+            assert!(is_steam_tool(&manifest(appid, name, installdir), Some(&info("game"))), "{}", name);
+        }
+    }
+
+    #[test]
+    fn falls_back_to_the_name_without_appinfo() {
+        assert!(is_steam_tool(&manifest(9_000_001, "Proton 11.0", "Proton 11.0"), None));
+        assert!(is_steam_tool(&manifest(9_000_002, "Steam Linux Runtime 4.0", "SteamLinuxRuntime_4"), None));
+        assert!(is_steam_tool(&manifest(9_000_003, "Portal 2 Dedicated Server", "Portal 2 DS"), None));
+        assert!(is_steam_tool(&manifest(9_000_004, "Runtime", "SteamLinuxRuntime_new"), None));
+        assert!(!is_steam_tool(&manifest(620, "Portal 2", "Portal 2"), None));
+        // Un type vide se comporte comme une absence de cache
+        assert!(!is_steam_tool(&manifest(620, "Portal 2", "Portal 2"), Some(&info(""))));
+    }
+}
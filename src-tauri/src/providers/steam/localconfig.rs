@@ -0,0 +1,127 @@
+use std::collections::HashMap;
+use std::fs;
+use std::path::Path;
+
+use crate::models::Game;
+use crate::vdf;
+
+// ===================== userdata/<id>/config/localconfig.vdf =====================
+// UserLocalConfigStore > Software > Valve > Steam > apps > <appid> { "LastPlayed" "Playtime" }
+
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct AppActivity {
+    /// Temps de jeu total, en minutes.
+    pub playtime_minutes: u64,
+    /// Dernière session, en secondes depuis l'epoch Unix (0 = jamais lancé).
+    pub last_played: u64,
+}
+
+/// Activité par appid pour un compte Steam.
+pub fn parse_localconfig(content: &str) -> HashMap<u32, AppActivity> {
+    let mut activity = HashMap::new();
+    let Ok(root) = vdf::parse(content) else { return activity };
+    let apps = root
+        .lookup(&["UserLocalConfigStore", "Software", "Valve", "Steam", "apps"])
+        .and_then(|v| v.as_obj());
+    let Some(apps) = apps else { return activity };
+
+    for (appid, app) in apps.iter() {
+        let (Ok(appid), Some(app)) = (appid.parse::<u32>(), app.as_obj()) else { continue };
+        let entry = AppActivity {
+            playtime_minutes: app.get_u64("Playtime").unwrap_or(0),
+            last_played: app.get_u64("LastPlayed").unwrap_or(0),
+        };
+        if entry != AppActivity::default() {
+            activity.insert(appid, entry);
+        }
+    }
+    activity
+}
+
+/// Activité fusionnée de tous les comptes présents sous `steam_root/userdata` :
+/// les temps de jeu s'additionnent, on garde la session la plus récente.
+pub fn read_activity(steam_root: &Path) -> HashMap<u32, AppActivity> {
+    let mut merged: HashMap<u32, AppActivity> = HashMap::new();
+    let Ok(users) = fs::read_dir(steam_root.join("userdata")) else { return merged };
+
+    for user in users.flatten() {
+        let path = user.path().join("config").join("localconfig.vdf");
+        let Ok(content) = fs::read_to_string(path) else { continue };
+        for (appid, activity) in parse_localconfig(&content) {
+            let total = merged.entry(appid).or_default();
+            total.playtime_minutes += activity.playtime_minutes;
+            total.last_played = total.last_played.max(activity.last_played);
+        }
+    }
+    merged
+}
+
+/// Reporte l'activité sur les jeux Steam (y compris les raccourcis non-Steam,
+/// dont l'id 64 bits contient l'appid 32 bits dans sa partie haute).
+pub fn apply_activity(games: &mut [Game], activity: &HashMap<u32, AppActivity>) {
+    for game in games.iter_mut() {
+        let Ok(id) = game.id.parse::<u64>() else { continue };
+        let appid = if id > u32::MAX as u64 { (id >> 32) as u32 } else { id as u32 };
+        if let Some(entry) = activity.get(&appid) {
+            game.playtime_minutes = entry.playtime_minutes;
+            game.last_played = entry.last_played;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Steam mélange les casses d'une version à l'autre ("Software"/"software", "apps"/"Apps")
+    fn localconfig(apps: &str) -> String {
+        format!(
+            "\"UserLocalConfigStore\"\n{{\n\t\"friends\" {{ \"PersonaName\" \"joueur\" }}\n\t\"software\"\n\t{{\n\t\t\"Valve\"\n\t\t{{\n\t\t\t\"steam\"\n\t\t\t{{\n\t\t\t\t\"Apps\"\n\t\t\t\t{{\n{}\n\t\t\t\t}}\n\t\t\t}}\n\t\t}}\n\t}}\n}}\n",
+            apps
+        )
+    }
+
+    #[test]
+    fn reads_playtime_and_last_played() {
+        let content = localconfig(
+            "\"620\" { \"LastPlayed\" \"1700000000\" \"Playtime\" \"754\" \"Playtime2wks\" \"12\" }\n\"730\" { \"cloud\" { \"quota\" \"0\" } }\n\"3054211584\" { \"lastplayed\" \"1690000000\" }\n\"pas_un_id\" { \"Playtime\" \"1\" }",
+        );
+        let activity = parse_localconfig(&content);
+        assert_eq!(activity.len(), 2);
+        assert_eq!(activity[&620], AppActivity { playtime_minutes: 754, last_played: 1_700_000_000 });
+        assert_eq!(activity[&3_054_211_584], AppActivity { playtime_minutes: 0, last_played: 1_690_000_000 });
+        assert!(parse_localconfig("\"UserLocalConfigStore\" {").is_empty());
+    }
+
+    #[test]
+    fn merges_every_account() {
+        let steam_root = std::env::temp_dir().join(format!("geewers-localconfig-{}", std::process::id()));
+        fs::remove_dir_all(&steam_root).ok();
+        let accounts = [
+            ("12345", "\"620\" { \"LastPlayed\" \"1700000000\" \"Playtime\" \"100\" }"),
+            ("67890", "\"620\" { \"LastPlayed\" \"1600000000\" \"Playtime\" \"50\" }\n\"440\" { \"Playtime\" \"7\" }"),
+        ];
+        for (user, apps) in accounts {
+            let config = steam_root.join("userdata").join(user).join("config");
+            fs::create_dir_all(&config).unwrap();
+            fs::write(config.join("localconfig.vdf"), localconfig(apps)).unwrap();
+        }
+        fs::create_dir_all(steam_root.join("userdata").join("0")).unwrap();
+
+        let activity = read_activity(&steam_root);
+        fs::remove_dir_all(&steam_root).ok();
+        assert_eq!(activity[&620], AppActivity { playtime_minutes: 150, last_played: 1_700_000_000 });
+        assert_eq!(activity[&440], AppActivity { playtime_minutes: 7, last_played: 0 });
+
+        // Raccourci non-Steam : l'appid est dans la partie haute de l'id 64 bits
+        let mut games = vec![
+            Game { id: "620".to_string(), ..Default::default() },
+            Game { id: ((440u64 << 32) | 0x0200_0000).to_string(), ..Default::default() },
+            Game { id: "999".to_string(), ..Default::default() },
+        ];
+        apply_activity(&mut games, &activity);
+        assert_eq!((games[0].playtime_minutes, games[0].last_played), (150, 1_700_000_000));
+        assert_eq!(games[1].playtime_minutes, 7);
+        assert_eq!(games[2].playtime_minutes, 0);
+    }
+}
@@ -0,0 +1,183 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::models::InstallState;
+use crate::vdf::{self, KeyValues, VdfValue};
+
+// ===================== appmanifest_<id>.acf =====================
+
+/// Contenu typé du bloc "AppState" d'un manifeste Steam.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct AppManifest {
+    pub appid: u32,
+    pub name: String,
+    pub installdir: String,
+    pub size_on_disk: u64,
+    pub state_flags: u32,
+    pub last_updated: u64,
+    pub last_owner: u64,
+    pub bytes_to_download: u64,
+    pub bytes_downloaded: u64,
+}
+
+// Bits de StateFlags (EAppState côté Steam)
+const STATE_UNINSTALLED: u32 = 1;
+const STATE_UPDATE_REQUIRED: u32 = 2;
+const STATE_FULLY_INSTALLED: u32 = 4;
+const STATE_FILES_MISSING: u32 = 32;
+const STATE_FILES_CORRUPT: u32 = 128;
+const STATE_UPDATE_RUNNING: u32 = 256;
+const STATE_UPDATE_PAUSED: u32 = 512;
+const STATE_UPDATE_STARTED: u32 = 1024;
+const STATE_UNINSTALLING: u32 = 2048;
+const STATE_ADDING_FILES: u32 = 262_144;
+const STATE_PREALLOCATING: u32 = 524_288;
+const STATE_DOWNLOADING: u32 = 1_048_576;
+const STATE_STAGING: u32 = 2_097_152;
+const STATE_COMMITTING: u32 = 4_194_304;
+
+const STATE_ANY_DOWNLOAD: u32 = STATE_UPDATE_RUNNING
+    | STATE_UPDATE_STARTED
+    | STATE_ADDING_FILES
+    | STATE_PREALLOCATING
+    | STATE_DOWNLOADING
+    | STATE_STAGING
+    | STATE_COMMITTING;
+
+impl AppManifest {
+    pub fn parse(content: &str) -> Option<Self> {
+        let root = vdf::parse(content).ok()?;
+        Self::from_keyvalues(root.get_obj("AppState")?)
+    }
+
+    pub fn from_file(path: &Path) -> Option<Self> {
+        Self::parse(&fs::read_to_string(path).ok()?)
+    }
+
+    pub fn from_keyvalues(app: &KeyValues) -> Option<Self> {
+        let appid = app.get_u64("appid")? as u32;
+        Some(Self {
+            appid,
+            name: app.get_str("name").unwrap_or_default().to_string(),
+            installdir: app.get_str("installdir").unwrap_or_default().to_string(),
+            size_on_disk: app.get_u64("SizeOnDisk").unwrap_or(0),
+            state_flags: app.get_u64("StateFlags").unwrap_or(0) as u32,
+            last_updated: app.get_u64("LastUpdated").unwrap_or(0),
+            last_owner: app.get_u64("LastOwner").unwrap_or(0),
+            bytes_to_download: app.get_u64("BytesToDownload").unwrap_or(0),
+            bytes_downloaded: app.get_u64("BytesDownloaded").unwrap_or(0),
+        })
+    }
+
+    /// Traduit StateFlags en état lisible. `game_dir_exists` permet de repérer
+    /// les manifestes orphelins (jeu supprimé à la main, manifeste resté en place).
+    pub fn install_state(&self, game_dir_exists: bool) -> InstallState {
+        let flags = self.state_flags;
+        if flags & (STATE_UNINSTALLED | STATE_UNINSTALLING) != 0 {
+            return InstallState::Uninstalled;
+        }
+        if flags & STATE_ANY_DOWNLOAD != 0 {
+            return InstallState::Downloading {
+                bytes_downloaded: self.bytes_downloaded,
+                bytes_to_download: self.bytes_to_download,
+            };
+        }
+        if flags & STATE_FULLY_INSTALLED != 0 && !game_dir_exists {
+            return InstallState::Uninstalled;
+        }
+        if flags & (STATE_UPDATE_REQUIRED | STATE_UPDATE_PAUSED | STATE_FILES_MISSING | STATE_FILES_CORRUPT) != 0 {
+            return InstallState::UpdateRequired;
+        }
+        if flags & STATE_FULLY_INSTALLED != 0 {
+            InstallState::Installed
+        } else if game_dir_exists {
+            // Ni installé ni en téléchargement : installation incomplète
+            InstallState::UpdateRequired
+        } else {
+            InstallState::Uninstalled
+        }
+    }
+}
+
+// ===================== libraryfolders.vdf =====================
+
+/// Chemins des bibliothèques déclarées dans libraryfolders.vdf.
+/// Gère le format actuel ("0" { "path" "..." }) et l'ancien ("1" "D:\\SteamLibrary").
+pub fn parse_library_folders(content: &str) -> Vec<PathBuf> {
+    let Ok(root) = vdf::parse(content) else { return Vec::new() };
+    let Some(folders) = root.get_obj("libraryfolders") else { return Vec::new() };
+
+    let mut paths = Vec::new();
+    for (key, value) in folders.iter() {
+        match value {
+            VdfValue::Obj(folder) => {
+                if let Some(path) = folder.get_str("path") {
+                    paths.push(PathBuf::from(path));
+                }
+            }
+            VdfValue::Str(path) if key.parse::<u32>().is_ok() => paths.push(PathBuf::from(path)),
+            VdfValue::Str(_) => {}
+        }
+    }
+    paths
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // appmanifest_620.acf tel qu'écrit par Steam (tabulations, clés en casse mixte)
+    const PORTAL_2: &str = "\"AppState\"\n{\n\t\"appid\"\t\t\"620\"\n\t\"Universe\"\t\t\"1\"\n\t\"name\"\t\t\"Portal 2\"\n\t\"StateFlags\"\t\t\"4\"\n\t\"installdir\"\t\t\"Portal 2\"\n\t\"LastUpdated\"\t\t\"1700000000\"\n\t\"SizeOnDisk\"\t\t\"12901927127\"\n\t\"buildid\"\t\t\"8773486\"\n\t\"LastOwner\"\t\t\"76561197960287930\"\n\t\"BytesToDownload\"\t\t\"0\"\n\t\"BytesDownloaded\"\t\t\"0\"\n\t\"InstalledDepots\"\n\t{\n\t\t\"621\"\n\t\t{\n\t\t\t\"manifest\"\t\t\"3405766893491213345\"\n\t\t\t\"size\"\t\t\"12000000000\"\n\t\t}\n\t}\n\t\"UserConfig\"\n\t{\n\t\t\"language\"\t\t\"english\"\n\t}\n}\n";
+
+    #[test]
+    fn parses_a_real_appmanifest() {
+        let manifest = AppManifest::parse(PORTAL_2).unwrap();
+        assert_eq!(manifest.appid, 620);
+        assert_eq!(manifest.name, "Portal 2");
+        assert_eq!(manifest.installdir, "Portal 2");
+        assert_eq!(manifest.size_on_disk, 12_901_927_127);
+        assert_eq!(manifest.last_owner, 76_561_197_960_287_930);
+        assert_eq!(manifest.install_state(true), InstallState::Installed);
+        assert_eq!(manifest.install_state(false), InstallState::Uninstalled);
+    }
+
+    fn with_state(flags: &str, downloaded: &str, to_download: &str) -> AppManifest {
+        let content = PORTAL_2
+            .replace("\"StateFlags\"\t\t\"4\"", &format!("\"StateFlags\"\t\t\"{}\"", flags))
+            .replace("\"BytesDownloaded\"\t\t\"0\"", &format!("\"BytesDownloaded\"\t\t\"{}\"", downloaded))
+            .replace("\"BytesToDownload\"\t\t\"0\"", &format!("\"BytesToDownload\"\t\t\"{}\"", to_download));
+        AppManifest::parse(&content).unwrap()
+    }
+
+    #[test]
+    fn reports_pending_updates() {
+        // 6 = FullyInstalled | UpdateRequired : mise à jour en attente
+        assert_eq!(with_state("6", "0", "0").install_state(true), InstallState::UpdateRequired);
+        // 516 = FullyInstalled | UpdatePaused
+        assert_eq!(with_state("516", "0", "0").install_state(true), InstallState::UpdateRequired);
+        // Dossier présent mais ni installé ni en téléchargement : installation incomplète
+        assert_eq!(with_state("0", "0", "0").install_state(true), InstallState::UpdateRequired);
+        assert_eq!(with_state("0", "0", "0").install_state(false), InstallState::Uninstalled);
+    }
+
+    #[test]
+    fn reports_download_progress() {
+        // 1026 = UpdateRequired | UpdateStarted : tel qu'écrit au début d'une installation
+        assert_eq!(
+            with_state("1026", "1048576", "4194304").install_state(false),
+            InstallState::Downloading { bytes_downloaded: 1_048_576, bytes_to_download: 4_194_304 }
+        );
+        // 1048838 = Downloading | UpdateRunning | UpdateRequired | FullyInstalled : le téléchargement l'emporte
+        assert_eq!(
+            with_state("1048838", "10", "20").install_state(true),
+            InstallState::Downloading { bytes_downloaded: 10, bytes_to_download: 20 }
+        );
+        // Une désinstallation en cours n'est jamais un téléchargement
+        assert_eq!(with_state("2052", "10", "20").install_state(true), InstallState::Uninstalled);
+    }
+
+    #[test]
+    fn rejects_a_truncated_appmanifest() {
+        assert_eq!(AppManifest::parse(&PORTAL_2[..PORTAL_2.len() / 2]), None);
+    }
+}
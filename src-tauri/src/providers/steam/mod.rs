@@ -0,0 +1,260 @@
+use std::collections::HashSet;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+use super::{home_dir, GameProvider};
+use crate::models::Game;
+use crate::registry::{Hive, RegistryReader};
+
+mod appinfo;
+mod classify;
+mod localconfig;
+mod manifest;
+mod shortcuts;
+
+pub use appinfo::{parse_appinfo, read_appinfo, AppInfo, LaunchEntry};
+pub use classify::is_steam_tool;
+pub use localconfig::{apply_activity, parse_localconfig, read_activity, AppActivity};
+pub use manifest::{parse_library_folders, AppManifest};
+pub use shortcuts::{get_shortcut_games, parse_shortcuts, shortcut_app_id, SteamShortcut};
+
+pub struct SteamProvider {
+    registry: Arc<dyn RegistryReader>,
+}
+
+impl SteamProvider {
+    pub fn new(registry: Arc<dyn RegistryReader>) -> Self {
+        Self { registry }
+    }
+}
+
+impl GameProvider for SteamProvider {
+    fn platform_id(&self) -> &str {
+        "Steam"
+    }
+
+    fn scan(&self) -> Vec<Game> {
+        get_steam_games(self.registry.as_ref())
+    }
+
+    fn launch(&self, id: &str, _exe_path: &str) {
+        // Les raccourcis non-Steam ont un identifiant 64 bits qui ne passe que par rungameid
+        if is_shortcut_id(id) {
+            opener::open(format!("steam://rungameid/{}", id)).ok();
+        } else {
+            opener::open(format!("steam://run/{}", id)).ok();
+        }
+    }
+
+    fn open_store_page(&self, id: &str) {
+        if is_shortcut_id(id) {
+            opener::open("steam://open/games").ok();
+        } else {
+            opener::open(format!("steam://nav/games/details/{}", id)).ok();
+        }
+    }
+}
+
+fn is_shortcut_id(id: &str) -> bool {
+    id.parse::<u64>().is_ok_and(|n| n > u32::MAX as u64)
+}
+
+pub fn get_steam_library_folders(steam_root: &Path) -> Vec<PathBuf> {
+    let mut folder_set = HashSet::new();
+    let mut folders = Vec::new();
+
+    let root_str = steam_root.to_string_lossy().to_string().to_lowercase();
+    folder_set.insert(root_str);
+    if let Ok(real_root) = fs::canonicalize(steam_root) {
+        folder_set.insert(real_root.to_string_lossy().to_lowercase());
+    }
+    folders.push(steam_root.to_path_buf());
+
+    let vdf_path = steam_root.join("steamapps").join("libraryfolders.vdf");
+    if let Ok(content) = fs::read_to_string(vdf_path) {
+        for p in parse_library_folders(&content) {
+            // Sous Linux, la bibliothèque principale est souvent déclarée via un lien symbolique du root
+            let real = fs::canonicalize(&p).unwrap_or_else(|_| p.clone());
+            let p_lower = real.to_string_lossy().to_lowercase();
+            if !folder_set.contains(&p_lower) && p.exists() {
+                folder_set.insert(p_lower);
+                folders.push(p);
+            }
+        }
+    }
+    folders
+}
+
+/// Tous les dossiers d'installation de Steam trouvés sur la machine :
+/// d'abord ceux du registre Windows (SteamPath de l'utilisateur, puis InstallPath de la machine),
+/// puis les emplacements Linux (natif, Flatpak, Snap).
+/// Les doublons (liens symboliques comme ~/.steam/steam) sont éliminés.
+pub fn find_steam_roots(registry: &dyn RegistryReader) -> Vec<PathBuf> {
+    let registry_paths = [
+        registry.value(Hive::CurrentUser, "Software\\Valve\\Steam", "SteamPath"),
+        registry.value(Hive::LocalMachine, "SOFTWARE\\WOW6432Node\\Valve\\Steam", "InstallPath"),
+        registry.value(Hive::LocalMachine, "SOFTWARE\\Valve\\Steam", "InstallPath"),
+    ];
+    let mut candidates: Vec<PathBuf> = registry_paths.into_iter().flatten().filter(|p| !p.is_empty()).map(PathBuf::from).collect();
+    if let Some(home) = home_dir() {
+        candidates.extend(linux_steam_roots(&home));
+    }
+
+    let mut seen = HashSet::new();
+    let mut roots = Vec::new();
+    for candidate in candidates {
+        if !candidate.join("steamapps").is_dir() { continue; }
+        let real = fs::canonicalize(&candidate).unwrap_or_else(|_| candidate.clone());
+        if seen.insert(real.to_string_lossy().to_lowercase()) {
+            roots.push(candidate);
+        }
+    }
+    roots
+}
+
+/// Emplacements possibles de Steam sous Linux (et Steam Deck), relatifs au dossier personnel.
+pub fn linux_steam_roots(home: &Path) -> Vec<PathBuf> {
+    vec![
+        home.join(".steam").join("steam"),
+        home.join(".steam").join("root"),
+        home.join(".local").join("share").join("Steam"),
+        // Flatpak
+        home.join(".var/app/com.valvesoftware.Steam/.local/share/Steam"),
+        home.join(".var/app/com.valvesoftware.Steam/data/Steam"),
+        // Snap
+        home.join("snap/steam/common/.local/share/Steam"),
+        home.join("snap/steam/common/.steam/steam"),
+    ]
+}
+
+pub fn get_steam_games(registry: &dyn RegistryReader) -> Vec<Game> {
+    find_steam_roots(registry)
+        .iter()
+        .flat_map(|root| get_steam_games_in(root))
+        .collect()
+}
+
+/// Scanne les manifestes de toutes les bibliothèques rattachées à `steam_root`,
+/// ainsi que les jeux non-Steam ajoutés par les utilisateurs.
+pub fn get_steam_games_in(steam_root: &Path) -> Vec<Game> {
+    let mut games = get_shortcut_games(steam_root);
+
+    // 1. Manifestes de toutes les bibliothèques
+    let mut installed = Vec::new();
+    for lib_path in get_steam_library_folders(steam_root) {
+        let apps_path = lib_path.join("steamapps");
+        if let Ok(entries) = fs::read_dir(&apps_path) {
+            for entry in entries.flatten() {
+                let filename = entry.file_name();
+                let filename_str = filename.to_string_lossy();
+
+                if filename_str.starts_with("appmanifest_") && filename_str.ends_with(".acf") {
+                    if let Some(manifest) = AppManifest::from_file(&entry.path()) {
+                        installed.push((lib_path.clone(), manifest));
+                    }
+                }
+            }
+        }
+    }
+
+    // 2. Métadonnées hors-ligne (appinfo.vdf) uniquement pour les apps installées
+    let wanted: HashSet<u32> = installed.iter().map(|(_, m)| m.appid).collect();
+    let app_infos = read_appinfo(steam_root, &wanted);
+
+    // 3. Conversion en Game
+    let library_cache = steam_root.join("appcache").join("librarycache");
+    for (lib_path, manifest) in installed {
+        let id = manifest.appid.to_string();
+        let info = app_infos.get(&manifest.appid);
+        let title = if !manifest.name.is_empty() {
+            manifest.name.clone()
+        } else {
+            info.map(|i| i.name.clone()).filter(|n| !n.is_empty()).unwrap_or_else(|| "Jeu Steam".to_string())
+        };
+
+        let cover = library_cache.join(format!("{}_library_600x900.jpg", id));
+        let img = if cover.exists() { cover.to_string_lossy().to_string() } else { "".to_string() };
+
+        // Exécutable réel d'après la config de lancement de Steam (le lancement passe toujours par steam://)
+        let game_dir = lib_path.join("steamapps").join("common").join(&manifest.installdir);
+        let (exe_path, launch_options) = match info.and_then(|i| i.primary_launch()) {
+            Some(launch) if !manifest.installdir.is_empty() => {
+                let exe = game_dir.join(launch.executable.replace('\\', std::path::MAIN_SEPARATOR_STR));
+                (exe.to_string_lossy().to_string(), launch.arguments.clone())
+            }
+            _ => ("".to_string(), "".to_string()),
+        };
+
+        let install_dir = lib_path.to_string_lossy().to_string();
+        let is_tool = is_steam_tool(&manifest, info);
+        let install_state = manifest.install_state(!manifest.installdir.is_empty() && game_dir.is_dir());
+
+        games.push(Game { 
+            id, title, platform: "Steam".to_string(), 
+            image_path: img, exe_path, install_dir,
+            is_favorite: false, launch_options, is_tool, install_state,
+            developer: info.map(|i| i.developer.clone()).unwrap_or_default(),
+            publisher: info.map(|i| i.publisher.clone()).unwrap_or_default(),
+            release_date: info.map(|i| i.release_date).unwrap_or(0),
+            ..Default::default()
+        });
+    }
+
+    // Temps de jeu et dernière session (localconfig.vdf de chaque compte)
+    apply_activity(&mut games, &read_activity(steam_root));
+    games
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::registry::MemoryRegistry;
+
+    #[test]
+    fn lists_native_flatpak_and_snap_roots() {
+        let roots = linux_steam_roots(Path::new("/home/deck"));
+        for expected in [
+            "/home/deck/.steam/steam",
+            "/home/deck/.local/share/Steam",
+            "/home/deck/.var/app/com.valvesoftware.Steam/.local/share/Steam",
+            "/home/deck/snap/steam/common/.local/share/Steam",
+        ] {
+            assert!(roots.contains(&PathBuf::from(expected)), "{}", expected);
+        }
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn deduplicates_symlinked_roots_and_libraries() {
+        let home = std::env::temp_dir().join(format!("geewers-steam-roots-{}", std::process::id()));
+        fs::remove_dir_all(&home).ok();
+        let real_root = home.join(".local").join("share").join("Steam");
+        let linked_root = home.join(".steam").join("steam");
+        let second_library = home.join("Jeux").join("SteamLibrary");
+        fs::create_dir_all(real_root.join("steamapps")).unwrap();
+        fs::create_dir_all(second_library.join("steamapps")).unwrap();
+        fs::create_dir_all(linked_root.parent().unwrap()).unwrap();
+        std::os::unix::fs::symlink(&real_root, &linked_root).unwrap();
+
+        // Même installation vue par le lien ~/.steam/steam et par son vrai chemin
+        let mut registry = MemoryRegistry::new();
+        registry.insert(Hive::CurrentUser, "Software\\Valve\\Steam", "SteamPath", &linked_root.to_string_lossy());
+        registry.insert(Hive::LocalMachine, "SOFTWARE\\Valve\\Steam", "InstallPath", &real_root.to_string_lossy());
+        let roots = find_steam_roots(&registry);
+        assert_eq!(roots.first(), Some(&linked_root));
+        assert!(!roots.contains(&real_root));
+
+        // libraryfolders.vdf redéclare la bibliothèque principale par son vrai chemin
+        let vdf = format!(
+            "\"libraryfolders\"\n{{\n\t\"0\" {{ \"path\" \"{}\" }}\n\t\"1\" {{ \"path\" \"{}\" }}\n\t\"2\" {{ \"path\" \"{}\" }}\n}}\n",
+            real_root.display(),
+            second_library.display(),
+            home.join("absent").display()
+        );
+        fs::write(real_root.join("steamapps").join("libraryfolders.vdf"), vdf).unwrap();
+        assert_eq!(get_steam_library_folders(&linked_root), vec![linked_root.clone(), second_library.clone()]);
+
+        fs::remove_dir_all(&home).ok();
+    }
+}
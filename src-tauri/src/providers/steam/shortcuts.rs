@@ -0,0 +1,177 @@
+use std::fs;
+use std::path::Path;
+
+use crate::models::Game;
+use crate::vdf::parse_binary;
+
+// ===================== userdata/<id>/config/shortcuts.vdf =====================
+// Les "jeux non-Steam" (émulateurs, autres launchers...) ajoutés par l'utilisateur.
+
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct SteamShortcut {
+    /// Identifiant 32 bits du raccourci (champ "appid", ou calculé pour les anciens fichiers).
+    pub app_id: u32,
+    pub app_name: String,
+    /// Chemins sans les guillemets que Steam ajoute autour.
+    pub exe: String,
+    pub start_dir: String,
+    pub launch_options: String,
+    pub icon: String,
+    pub is_hidden: bool,
+}
+
+impl SteamShortcut {
+    /// Identifiant 64 bits attendu par `steam://rungameid/`.
+    pub fn game_id(&self) -> u64 {
+        ((self.app_id as u64) << 32) | 0x0200_0000
+    }
+}
+
+/// Identifiant historique d'un raccourci : CRC32 de l'exe (tel qu'écrit, guillemets compris)
+/// concaténé au nom, avec le bit de poids fort forcé.
+pub fn shortcut_app_id(raw_exe: &str, app_name: &str) -> u32 {
+    crc32fast::hash(format!("{}{}", raw_exe, app_name).as_bytes()) | 0x8000_0000
+}
+
+pub fn parse_shortcuts(data: &[u8]) -> Vec<SteamShortcut> {
+    let Ok(root) = parse_binary(data) else { return Vec::new() };
+    let Some(list) = root.get_obj("shortcuts") else { return Vec::new() };
+
+    let mut shortcuts = Vec::new();
+    for (_, entry) in list.iter() {
+        let Some(entry) = entry.as_obj() else { continue };
+        let text = |key: &str| entry.get_str(key).unwrap_or_default().to_string();
+
+        let app_name = text("AppName");
+        let raw_exe = text("Exe");
+        if app_name.is_empty() || raw_exe.is_empty() { continue; }
+
+        // L'appid est écrit en int32 signé : on récupère sa représentation 32 bits
+        let app_id = entry
+            .get_str("appid")
+            .and_then(|s| s.parse::<i64>().ok())
+            .map(|n| n as u32)
+            .filter(|&n| n != 0)
+            .unwrap_or_else(|| shortcut_app_id(&raw_exe, &app_name));
+
+        shortcuts.push(SteamShortcut {
+            app_id,
+            app_name,
+            exe: unquote(&raw_exe),
+            start_dir: unquote(&text("StartDir")),
+            launch_options: text("LaunchOptions"),
+            icon: unquote(&text("icon")),
+            is_hidden: entry.get_str("IsHidden").is_some_and(|v| v != "0"),
+        });
+    }
+    shortcuts
+}
+
+/// Raccourcis non-Steam de tous les comptes présents sous `steam_root/userdata`.
+pub fn get_shortcut_games(steam_root: &Path) -> Vec<Game> {
+    let mut games = Vec::new();
+    let Ok(users) = fs::read_dir(steam_root.join("userdata")) else { return games };
+
+    for user in users.flatten() {
+        let config_dir = user.path().join("config");
+        let Ok(data) = fs::read(config_dir.join("shortcuts.vdf")) else { continue };
+
+        for shortcut in parse_shortcuts(&data) {
+            if shortcut.is_hidden { continue; }
+            let image_path = find_grid_image(&config_dir, shortcut.app_id)
+                .unwrap_or_else(|| if Path::new(&shortcut.icon).is_file() { shortcut.icon.clone() } else { "".to_string() });
+
+            games.push(Game {
+                id: shortcut.game_id().to_string(),
+                title: shortcut.app_name,
+                platform: "Steam".to_string(),
+                image_path,
+                exe_path: shortcut.exe,
+                install_dir: shortcut.start_dir,
+                launch_options: shortcut.launch_options,
+                ..Default::default()
+            });
+        }
+    }
+    games
+}
+
+// Jaquette personnalisée choisie dans Steam : config/grid/<appid>p.png (format portrait)
+fn find_grid_image(config_dir: &Path, app_id: u32) -> Option<String> {
+    let grid = config_dir.join("grid");
+    ["png", "jpg", "jpeg", "webp"]
+        .iter()
+        .map(|ext| grid.join(format!("{}p.{}", app_id, ext)))
+        .find(|p| p.is_file())
+        .map(|p| p.to_string_lossy().to_string())
+}
+
+fn unquote(value: &str) -> String {
+    value.trim().trim_matches('"').to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn string(data: &mut Vec<u8>, key: &str, value: &str) {
+        data.push(0x01);
+        data.extend(key.as_bytes());
+        data.push(0);
+        data.extend(value.as_bytes());
+        data.push(0);
+    }
+
+    // shortcuts.vdf avec deux raccourcis : le premier sans appid (ancien fichier), le second avec
+    fn fixture() -> Vec<u8> {
+        let mut data = b"\x00shortcuts\0".to_vec();
+        data.extend(b"\x000\0");
+        string(&mut data, "AppName", "6789");
+        string(&mut data, "Exe", "12345");
+        string(&mut data, "StartDir", "\"C:\\Emu\\\"");
+        data.extend(b"\x02IsHidden\0\x00\x00\x00\x00");
+        data.push(0x08);
+        data.extend(b"\x001\0");
+        data.extend(b"\x02appid\0");
+        data.extend((-1_295_049_910i32).to_le_bytes());
+        string(&mut data, "AppName", "RetroArch");
+        string(&mut data, "Exe", "\"C:\\RetroArch\\retroarch.exe\"");
+        string(&mut data, "LaunchOptions", "-f");
+        data.extend(b"\x02IsHidden\0\x01\x00\x00\x00");
+        data.extend([0x08, 0x08, 0x08]);
+        data
+    }
+
+    #[test]
+    fn parses_two_shortcuts() {
+        let shortcuts = parse_shortcuts(&fixture());
+        assert_eq!(shortcuts.len(), 2);
+
+        // CRC32("123456789") = 0xCBF43926, la valeur de contrôle standard du CRC32
+        assert_eq!(shortcuts[0].app_id, 0xCBF4_3926);
+        assert_eq!(shortcuts[0].game_id(), 0xCBF4_3926_0200_0000);
+        assert_eq!(shortcuts[0].start_dir, "C:\\Emu\\");
+        assert!(!shortcuts[0].is_hidden);
+
+        assert_eq!(shortcuts[1].app_id, 2_999_917_386);
+        assert_eq!(shortcuts[1].exe, "C:\\RetroArch\\retroarch.exe");
+        assert_eq!(shortcuts[1].launch_options, "-f");
+        assert!(shortcuts[1].is_hidden);
+    }
+
+    #[test]
+    fn computes_the_legacy_app_id_like_steam() {
+        assert_eq!(shortcut_app_id("12345", "6789"), 0xCBF4_3926);
+        // Le bit de poids fort est toujours forcé
+        assert!(shortcut_app_id("a", "b") & 0x8000_0000 != 0);
+    }
+
+    #[test]
+    fn truncated_files_do_not_panic() {
+        let data = fixture();
+        for len in 0..data.len() {
+            let shortcuts = parse_shortcuts(&data[..len]);
+            assert!(shortcuts.len() <= 2);
+        }
+    }
+}
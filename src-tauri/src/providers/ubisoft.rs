@@ -0,0 +1,358 @@
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+use super::{scavenge_image, GameProvider};
+use crate::models::Game;
+use crate::protobuf::{ProtoReader, ProtoValue};
+use crate::registry::{Hive, RegistryReader};
+
+// ===================== UBISOFT CONNECT =====================
+// Les jeux installés sont les sous-clés HKLM\SOFTWARE\WOW6432Node\Ubisoft\Launcher\Installs\<install id>.
+// Le reste vient du cache du launcher (<launcher>\cache\configuration\configurations) : une suite
+// de messages protobuf { 1: install id, 2: launch id, 3: configuration YAML }, le YAML donnant
+//   root.name, root.thumb_image (jaquette dans <launcher>\cache\assets\), root.start_game.*.executables
+// avec des valeurs souvent indirectes : clés de localizations.default (ex: name: l1).
+
+const LAUNCHER_KEY: &str = "SOFTWARE\\WOW6432Node\\Ubisoft\\Launcher";
+const INSTALLS_KEY: &str = "SOFTWARE\\WOW6432Node\\Ubisoft\\Launcher\\Installs";
+const DEFAULT_LAUNCHER_DIR: &str = "C:\\Program Files (x86)\\Ubisoft\\Ubisoft Game Launcher";
+
+/// Une entrée du cache de configurations.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct UbisoftConfiguration {
+    pub install_id: u64,
+    pub launch_id: u64,
+    pub yaml: String,
+}
+
+/// Les informations utiles du YAML d'un jeu.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct UbisoftGameInfo {
+    pub name: String,
+    /// Noms de fichiers dans cache\assets, du plus adapté (jaquette) au moins adapté
+    pub images: Vec<String>,
+    /// Exécutables relatifs au dossier d'installation (online puis offline), par ordre de préférence
+    pub executables: Vec<String>,
+}
+
+pub struct UbisoftProvider {
+    registry: Arc<dyn RegistryReader>,
+    launcher_dir: Option<PathBuf>,
+}
+
+impl UbisoftProvider {
+    pub fn new(registry: Arc<dyn RegistryReader>) -> Self {
+        Self { registry, launcher_dir: None }
+    }
+
+    /// Provider lisant le cache d'un dossier de launcher précis (copie d'un PC Windows, fixture...).
+    pub fn with_launcher_dir(registry: Arc<dyn RegistryReader>, launcher_dir: PathBuf) -> Self {
+        Self { registry, launcher_dir: Some(launcher_dir) }
+    }
+
+    fn launcher_dir(&self) -> PathBuf {
+        self.launcher_dir.clone().unwrap_or_else(|| {
+            self.registry
+                .value(Hive::LocalMachine, LAUNCHER_KEY, "InstallDir")
+                .filter(|d| !d.is_empty())
+                .map(PathBuf::from)
+                .unwrap_or_else(|| PathBuf::from(DEFAULT_LAUNCHER_DIR))
+        })
+    }
+}
+
+impl GameProvider for UbisoftProvider {
+    fn platform_id(&self) -> &str {
+        "Ubisoft"
+    }
+
+    fn scan(&self) -> Vec<Game> {
+        let launcher_dir = self.launcher_dir();
+        let assets_dir = launcher_dir.join("cache").join("assets");
+        let configurations = fs::read(launcher_dir.join("cache").join("configuration").join("configurations"))
+            .map(|data| parse_configurations(&data))
+            .unwrap_or_default();
+        let by_install_id: HashMap<u64, &UbisoftConfiguration> = configurations.iter().map(|c| (c.install_id, c)).collect();
+
+        let mut games = Vec::new();
+        for subkey_name in self.registry.subkeys(Hive::LocalMachine, INSTALLS_KEY) {
+            let subkey = format!("{}\\{}", INSTALLS_KEY, subkey_name);
+            let Some(install_dir) = self.registry.value(Hive::LocalMachine, &subkey, "InstallDir") else { continue };
+            if !Path::new(&install_dir).exists() { continue; }
+
+            let info = subkey_name
+                .parse::<u64>()
+                .ok()
+                .and_then(|id| by_install_id.get(&id))
+                .and_then(|c| parse_game_yaml(&c.yaml))
+                .unwrap_or_default();
+            let title = if info.name.is_empty() {
+                Path::new(&install_dir).file_name().unwrap_or_default().to_string_lossy().to_string()
+            } else {
+                info.name.clone()
+            };
+            let image_path = info
+                .images
+                .iter()
+                .map(|image| assets_dir.join(image))
+                .find(|p| p.is_file())
+                .map(|p| p.to_string_lossy().to_string())
+                .unwrap_or_else(|| scavenge_image(&install_dir));
+            let exe_path = info
+                .executables
+                .iter()
+                // Chemins écrits avec des "\" : on les recompose morceau par morceau
+                .map(|exe| exe.split(['\\', '/']).filter(|part| !part.is_empty()).fold(PathBuf::from(&install_dir), |path, part| path.join(part)))
+                .find(|p| p.is_file())
+                .map(|p| p.to_string_lossy().to_string())
+                .unwrap_or_default();
+
+            games.push(Game {
+                id: subkey_name.clone(), title, platform: "Ubisoft".to_string(),
+                image_path, exe_path, install_dir, is_favorite: false, ..Default::default()
+            });
+        }
+        games
+    }
+
+    // Ubisoft Connect gère la connexion et les mises à jour ; sans lui, on lance l'exécutable du jeu
+    fn launch(&self, id: &str, exe_path: &str) {
+        if opener::open(format!("uplay://launch/{}/0", id)).is_err() && !exe_path.is_empty() {
+            opener::open(exe_path).ok();
+        }
+    }
+
+    fn open_store_page(&self, _id: &str) {
+        opener::open("uplay://").ok();
+    }
+}
+
+/// Décode le cache de configurations. Les entrées illisibles sont ignorées.
+pub fn parse_configurations(data: &[u8]) -> Vec<UbisoftConfiguration> {
+    let mut configurations = Vec::new();
+    for field in ProtoReader::new(data).fields() {
+        let Ok((1, ProtoValue::Bytes(record))) = field else { continue };
+        let mut configuration = UbisoftConfiguration::default();
+        let mut corrupt = false;
+        for field in ProtoReader::new(record).fields() {
+            match field {
+                Ok((1, ProtoValue::Varint(id))) => configuration.install_id = id,
+                Ok((2, ProtoValue::Varint(id))) => configuration.launch_id = id,
+                Ok((3, ProtoValue::Bytes(yaml))) => configuration.yaml = String::from_utf8_lossy(yaml).trim_end_matches('\0').to_string(),
+                Ok(_) => {}
+                Err(_) => corrupt = true,
+            }
+        }
+        // Entrée tronquée ou illisible : mieux vaut aucune configuration qu'une configuration fausse
+        if corrupt { continue; }
+        if configuration.launch_id == 0 {
+            configuration.launch_id = configuration.install_id;
+        }
+        if configuration.install_id != 0 && !configuration.yaml.is_empty() {
+            configurations.push(configuration);
+        }
+    }
+    configurations
+}
+
+/// Lit le YAML de configuration d'un jeu.
+pub fn parse_game_yaml(yaml: &str) -> Option<UbisoftGameInfo> {
+    let doc: serde_yaml::Value = serde_yaml::from_str(yaml).ok()?;
+    let root = doc.get("root")?;
+    let localizations = doc.get("localizations").and_then(|l| l.get("default"));
+    // Une valeur peut être une clé de localizations.default (ex: name: l1 -> "Far Cry 5")
+    let localized = |value: Option<&serde_yaml::Value>| -> String {
+        let raw = value.and_then(|v| v.as_str()).unwrap_or("").trim();
+        localizations
+            .and_then(|l| l.get(raw))
+            .and_then(|v| v.as_str())
+            .unwrap_or(raw)
+            .trim()
+            .to_string()
+    };
+
+    let images = ["thumb_image", "logo_image", "background_image"]
+        .iter()
+        .map(|key| localized(root.get(*key)))
+        .filter(|image| !image.is_empty())
+        .collect();
+
+    let mut executables = Vec::new();
+    for mode in ["online", "offline"] {
+        let list = root.get("start_game").and_then(|s| s.get(mode)).and_then(|m| m.get("executables")).and_then(|e| e.as_sequence());
+        for executable in list.into_iter().flatten() {
+            let text = |node: Option<&serde_yaml::Value>, key: &str| {
+                node.and_then(|n| n.get(key)).and_then(|v| v.as_str()).unwrap_or("").trim().to_string()
+            };
+            let path = executable.get("path");
+            // "relative" est relatif au dossier de travail (working_directory.append) ou, selon les jeux,
+            // au dossier d'installation : on essaie les deux. "append" complète la clé InstallDir.
+            let relative = text(path, "relative");
+            let working_dir = text(executable.get("working_directory"), "append");
+            let mut candidates = Vec::new();
+            if !relative.is_empty() && !working_dir.is_empty() {
+                candidates.push(format!("{}\\{}", working_dir.trim_end_matches(['\\', '/']), relative));
+            }
+            candidates.push(relative);
+            candidates.push(text(path, "append"));
+            for candidate in candidates {
+                if !candidate.is_empty() && !executables.contains(&candidate) {
+                    executables.push(candidate);
+                }
+            }
+        }
+    }
+
+    Some(UbisoftGameInfo { name: localized(root.get("name")), images, executables })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::registry::MemoryRegistry;
+
+    // YAML d'un jeu tel qu'on le trouve dans le cache : nom et logo indirects, exécutable online
+    // relatif au dossier de travail, exécutable offline sous la clé InstallDir
+    const FAR_CRY_5: &str = r"version: 2.0
+root:
+  name: l1
+  thumb_image: 3e4f1a2b5c6d7e8f.jpg
+  logo_image: l2
+  background_image: ''
+  start_game:
+    online:
+      executables:
+      - shortcut_name: l1
+        path:
+          relative: FarCry5.exe
+        working_directory:
+          register: HKEY_LOCAL_MACHINE\SOFTWARE\Ubisoft\Launcher\Installs\4311\InstallDir
+          append: bin\
+    offline:
+      executables:
+      - path:
+          register: HKEY_LOCAL_MACHINE\SOFTWARE\Ubisoft\Launcher\Installs\4311\InstallDir
+          append: bin\FarCry5_offline.exe
+localizations:
+  default:
+    l1: Far Cry 5
+    l2: 9a8b7c6d5e4f.png
+  fr-FR:
+    l1: Far Cry 5 (FR)
+";
+
+    fn varint(mut value: u64, out: &mut Vec<u8>) {
+        while value >= 0x80 {
+            out.push((value as u8) | 0x80);
+            value >>= 7;
+        }
+        out.push(value as u8);
+    }
+
+    fn bytes_field(number: u64, content: &[u8], out: &mut Vec<u8>) {
+        varint(number << 3 | 2, out);
+        varint(content.len() as u64, out);
+        out.extend_from_slice(content);
+    }
+
+    // Message { 1: install id, 2: launch id, 3: YAML } encadré comme dans le fichier configurations
+    fn record(install_id: u64, launch_id: u64, yaml: &str) -> Vec<u8> {
+        let mut message = Vec::new();
+        varint(1 << 3, &mut message);
+        varint(install_id, &mut message);
+        if launch_id != 0 {
+            varint(2 << 3, &mut message);
+            varint(launch_id, &mut message);
+        }
+        bytes_field(3, yaml.as_bytes(), &mut message);
+        let mut framed = Vec::new();
+        bytes_field(1, &message, &mut framed);
+        framed
+    }
+
+    #[test]
+    fn parses_game_yaml() {
+        let info = parse_game_yaml(FAR_CRY_5).unwrap();
+        assert_eq!(info.name, "Far Cry 5");
+        assert_eq!(info.images, ["3e4f1a2b5c6d7e8f.jpg", "9a8b7c6d5e4f.png"]);
+        assert_eq!(info.executables, ["bin\\FarCry5.exe", "FarCry5.exe", "bin\\FarCry5_offline.exe"]);
+
+        // Nom écrit en clair, sans localizations
+        let plain = parse_game_yaml("root:\n  name: Rayman Legends\n").unwrap();
+        assert_eq!(plain.name, "Rayman Legends");
+        assert!(plain.executables.is_empty());
+        assert_eq!(parse_game_yaml("version: 2.0\n"), None);
+        assert_eq!(parse_game_yaml("root: [ pas du yaml"), None);
+    }
+
+    #[test]
+    fn parses_framed_records_and_drops_broken_ones() {
+        let mut data = record(4311, 5595, FAR_CRY_5);
+        // YAML terminé par des zéros, launch id absent : l'install id en tient lieu
+        data.extend(record(635, 0, "root:\n  name: Rayman Legends\n\0\0"));
+        // Entrée dont le YAML annonce plus d'octets que le message n'en contient
+        let mut garbage = vec![0x08, 0x96, 0x01, 0x1A, 0x7F];
+        garbage.extend_from_slice(b"root:\n  name: Faux\n");
+        bytes_field(1, &garbage, &mut data);
+        // Entrée sans YAML, puis une entrée coupée en fin de fichier
+        data.extend([0x0A, 0x02, 0x08, 0x2A]);
+        data.extend(&record(720, 0, FAR_CRY_5)[..20]);
+
+        let configurations = parse_configurations(&data);
+        assert_eq!(configurations.len(), 2);
+        assert_eq!(configurations[0].install_id, 4311);
+        assert_eq!(configurations[0].launch_id, 5595);
+        assert_eq!(configurations[0].yaml, FAR_CRY_5);
+        assert_eq!(configurations[1], UbisoftConfiguration {
+            install_id: 635,
+            launch_id: 635,
+            yaml: "root:\n  name: Rayman Legends\n".to_string(),
+        });
+        assert!(parse_configurations(b"\xFF\xFF\xFF").is_empty());
+    }
+
+    #[test]
+    fn scans_with_cached_titles_covers_and_executables() {
+        let root = std::env::temp_dir().join(format!("geewers-ubisoft-{}", std::process::id()));
+        fs::remove_dir_all(&root).ok();
+        let launcher_dir = root.join("Ubisoft Game Launcher");
+        let far_cry = root.join("Games").join("Far Cry 5");
+        let rayman = root.join("Games").join("Rayman Legends");
+        fs::create_dir_all(launcher_dir.join("cache").join("configuration")).unwrap();
+        fs::create_dir_all(launcher_dir.join("cache").join("assets")).unwrap();
+        fs::create_dir_all(far_cry.join("bin")).unwrap();
+        fs::create_dir_all(&rayman).unwrap();
+        fs::write(far_cry.join("bin").join("FarCry5.exe"), b"").unwrap();
+        fs::write(launcher_dir.join("cache").join("assets").join("9a8b7c6d5e4f.png"), b"").unwrap();
+
+        let mut data = record(4311, 0, FAR_CRY_5);
+        data.extend(&record(635, 0, "root:\n  name: Rayman Legends\n")[..12]);
+        fs::write(launcher_dir.join("cache").join("configuration").join("configurations"), data).unwrap();
+
+        let mut registry = MemoryRegistry::new();
+        registry.insert(Hive::LocalMachine, &format!("{}\\4311", INSTALLS_KEY), "InstallDir", &far_cry.to_string_lossy());
+        registry.insert(Hive::LocalMachine, &format!("{}\\635", INSTALLS_KEY), "InstallDir", &rayman.to_string_lossy());
+
+        let mut games = UbisoftProvider::with_launcher_dir(Arc::new(registry), launcher_dir.clone()).scan();
+        games.sort_by(|a, b| a.id.cmp(&b.id));
+        assert_eq!(games.len(), 2);
+
+        let far_cry_5 = &games[0];
+        assert_eq!(far_cry_5.id, "4311");
+        assert_eq!(far_cry_5.title, "Far Cry 5");
+        // Jaquette absente du cache : l'image suivante (logo) est retenue
+        assert_eq!(far_cry_5.image_path, launcher_dir.join("cache").join("assets").join("9a8b7c6d5e4f.png").to_string_lossy());
+        // bin\ du dossier de travail joint à l'exécutable relatif
+        assert_eq!(far_cry_5.exe_path, far_cry.join("bin").join("FarCry5.exe").to_string_lossy());
+
+        // Entrée coupée : le jeu reste, avec le nom de son dossier plutôt qu'un titre erroné
+        assert_eq!(games[1].id, "635");
+        assert_eq!(games[1].title, "Rayman Legends");
+        assert_eq!(games[1].exe_path, "");
+        assert_eq!(games[1].image_path, "");
+
+        fs::remove_dir_all(&root).ok();
+    }
+}
@@ -0,0 +1,339 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+use sha2::{Digest, Sha256};
+
+use super::{scavenge_image, system_drives, GameProvider};
+use crate::models::{Game, UserData};
+
+// ===================== XBOX / MICROSOFT STORE =====================
+// Les jeux du Game Pass PC s'installent dans un dossier "XboxGames" à la racine d'un disque
+// (le nom réel est écrit dans <disque>\.GamingRoot). Chaque jeu a un sous-dossier Content\ avec :
+// - MicrosoftGame.config : identité du paquet, exécutables, nom affiché et logos
+// - appxmanifest.xml : le manifeste du paquet installé (même infos, format MSIX)
+// On lance via shell:AppsFolder\<AUMID>, où AUMID = <PackageFamilyName>!<AppId>.
+
+/// Infos utiles tirées d'un MicrosoftGame.config ou d'un appxmanifest.xml.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct XboxManifest {
+    /// Identity Name (ex: "Microsoft.624F8B84B80")
+    pub identity_name: String,
+    /// Identity Publisher (ex: "CN=...")
+    pub publisher: String,
+    /// Id de l'application dans le paquet (souvent "Game")
+    pub app_id: String,
+    pub title: String,
+    /// Exécutable relatif au dossier Content
+    pub executable: String,
+    /// Logo relatif au dossier Content (sans qualificatif d'échelle)
+    pub logo: String,
+}
+
+pub struct XboxProvider;
+
+impl GameProvider for XboxProvider {
+    fn platform_id(&self) -> &str {
+        "Xbox"
+    }
+
+    fn scan(&self) -> Vec<Game> {
+        get_xbox_games(&system_drives())
+    }
+
+    // Seuls les disques choisis par l'utilisateur sont parcourus (tous si aucun)
+    fn scan_with_settings(&self, settings: &UserData) -> Vec<Game> {
+        if settings.selected_drives.is_empty() {
+            return self.scan();
+        }
+        let mut drives: Vec<String> = settings.selected_drives.iter().cloned().collect();
+        drives.sort();
+        get_xbox_games(&drives)
+    }
+
+    fn launch(&self, id: &str, _exe_path: &str) {
+        opener::open(format!("shell:AppsFolder\\{}", id)).ok();
+    }
+
+    fn open_store_page(&self, _id: &str) {
+        opener::open("ms-windows-store://downloadsandupdates").ok();
+    }
+}
+
+/// Jeux installés dans les dossiers XboxGames des disques donnés.
+pub fn get_xbox_games(drives: &[String]) -> Vec<Game> {
+    let mut games: Vec<Game> = Vec::new();
+    for drive in drives {
+        for root in xbox_game_roots(Path::new(drive)) {
+            let Ok(entries) = fs::read_dir(&root) else { continue };
+            for entry in entries.flatten() {
+                let Some(game) = read_xbox_game(&entry.path()) else { continue };
+                if !games.iter().any(|g| g.id == game.id) {
+                    games.push(game);
+                }
+            }
+        }
+    }
+    games
+}
+
+/// Dossiers de jeux d'un disque : ceux listés dans .GamingRoot, sinon "XboxGames".
+pub fn xbox_game_roots(drive: &Path) -> Vec<PathBuf> {
+    let mut names = fs::read(drive.join(".GamingRoot")).map(|data| parse_gaming_root(&data)).unwrap_or_default();
+    if names.is_empty() {
+        names.push("XboxGames".to_string());
+    }
+    names
+        .iter()
+        .map(|name| drive.join(name.trim_start_matches(['\\', '/'])))
+        .filter(|p| p.is_dir())
+        .collect()
+}
+
+/// .GamingRoot : "RGBX", nombre d'entrées (u32), puis des chemins UTF-16 terminés par \0.
+pub fn parse_gaming_root(data: &[u8]) -> Vec<String> {
+    if data.len() < 8 || &data[..4] != b"RGBX" { return Vec::new(); }
+    let count = u32::from_le_bytes([data[4], data[5], data[6], data[7]]) as usize;
+    let units: Vec<u16> = data[8..].chunks_exact(2).map(|c| u16::from_le_bytes([c[0], c[1]])).collect();
+    units
+        .split(|&u| u == 0)
+        .filter(|s| !s.is_empty())
+        .take(count)
+        .map(String::from_utf16_lossy)
+        .collect()
+}
+
+/// Lit un dossier de jeu (<racine>\<jeu>) et construit le `Game` correspondant.
+pub fn read_xbox_game(game_dir: &Path) -> Option<Game> {
+    let content_dir = Some(game_dir.join("Content")).filter(|c| c.is_dir()).unwrap_or_else(|| game_dir.to_path_buf());
+    let read = |name: &str| fs::read_to_string(content_dir.join(name)).ok();
+
+    let config = read("MicrosoftGame.config").and_then(|xml| parse_microsoft_game_config(&xml));
+    let appx = read("appxmanifest.xml").and_then(|xml| parse_appx_manifest(&xml));
+    let manifest = merge_manifests(config, appx)?;
+    if manifest.identity_name.is_empty() || manifest.publisher.is_empty() { return None; }
+
+    let app_id = if manifest.app_id.is_empty() { "Game".to_string() } else { manifest.app_id.clone() };
+    let aumid = format!("{}!{}", package_family_name(&manifest.identity_name, &manifest.publisher), app_id);
+
+    let install_dir = content_dir.to_string_lossy().to_string();
+    let title = Some(manifest.title.clone())
+        .filter(|t| !t.is_empty())
+        .unwrap_or_else(|| game_dir.file_name().unwrap_or_default().to_string_lossy().to_string());
+    let exe_path = if manifest.executable.is_empty() {
+        "".to_string()
+    } else {
+        content_dir.join(&manifest.executable).to_string_lossy().to_string()
+    };
+    let image_path = resolve_asset(&content_dir, &manifest.logo).unwrap_or_else(|| scavenge_image(&install_dir));
+
+    Some(Game {
+        id: aumid,
+        title,
+        platform: "Xbox".to_string(),
+        image_path,
+        exe_path,
+        install_dir,
+        ..Default::default()
+    })
+}
+
+// L'appxmanifest fait foi pour l'identité du paquet installé, MicrosoftGame.config complète le reste
+fn merge_manifests(config: Option<XboxManifest>, appx: Option<XboxManifest>) -> Option<XboxManifest> {
+    match (config, appx) {
+        (Some(config), Some(appx)) => {
+            let pick = |a: &String, b: &String| if a.is_empty() { b.clone() } else { a.clone() };
+            Some(XboxManifest {
+                identity_name: pick(&appx.identity_name, &config.identity_name),
+                publisher: pick(&appx.publisher, &config.publisher),
+                app_id: pick(&appx.app_id, &config.app_id),
+                title: pick(&config.title, &appx.title),
+                executable: pick(&config.executable, &appx.executable),
+                logo: pick(&config.logo, &appx.logo),
+            })
+        }
+        (config, appx) => config.or(appx),
+    }
+}
+
+// Les noms "ms-resource:..." sont des références vers resources.pri, illisibles telles quelles
+fn display_text(value: Option<&str>) -> String {
+    value.filter(|v| !v.is_empty() && !v.starts_with("ms-resource:")).unwrap_or("").to_string()
+}
+
+/// Parse un MicrosoftGame.config.
+pub fn parse_microsoft_game_config(xml: &str) -> Option<XboxManifest> {
+    let doc = roxmltree::Document::parse(xml).ok()?;
+    let root = doc.root_element();
+    if root.tag_name().name() != "Game" { return None; }
+    let child = |name: &str| root.descendants().find(|n| n.tag_name().name() == name);
+
+    let identity = child("Identity");
+    let executable = root
+        .descendants()
+        .filter(|n| n.tag_name().name() == "Executable")
+        .find(|n| n.attribute("TargetDeviceFamily").is_none_or(|f| f.eq_ignore_ascii_case("PC")));
+    let visuals = child("ShellVisuals");
+    let logo = visuals
+        .and_then(|v| ["Square480x480Logo", "Square150x150Logo", "StoreLogo", "Square44x44Logo"].iter().find_map(|a| v.attribute(*a)))
+        .unwrap_or("");
+
+    Some(XboxManifest {
+        identity_name: identity.and_then(|i| i.attribute("Name")).unwrap_or("").to_string(),
+        publisher: identity.and_then(|i| i.attribute("Publisher")).unwrap_or("").to_string(),
+        app_id: executable.and_then(|e| e.attribute("Id")).unwrap_or("").to_string(),
+        title: display_text(visuals.and_then(|v| v.attribute("DefaultDisplayName"))),
+        executable: executable.and_then(|e| e.attribute("Name")).unwrap_or("").to_string(),
+        logo: logo.to_string(),
+    })
+}
+
+/// Parse un appxmanifest.xml (première application du paquet).
+pub fn parse_appx_manifest(xml: &str) -> Option<XboxManifest> {
+    let doc = roxmltree::Document::parse(xml).ok()?;
+    let root = doc.root_element();
+    if root.tag_name().name() != "Package" { return None; }
+    let find = |name: &str| root.descendants().find(|n| n.tag_name().name() == name);
+
+    let identity = find("Identity");
+    let application = find("Application");
+    let visuals = application.and_then(|a| a.descendants().find(|n| n.tag_name().name() == "VisualElements"));
+    let properties_name = find("Properties")
+        .and_then(|p| p.children().find(|n| n.tag_name().name() == "DisplayName"))
+        .and_then(|n| n.text());
+
+    let title = Some(display_text(visuals.and_then(|v| v.attribute("DisplayName"))))
+        .filter(|t| !t.is_empty())
+        .unwrap_or_else(|| display_text(properties_name));
+    let logo = visuals
+        .and_then(|v| ["Square150x150Logo", "Square44x44Logo"].iter().find_map(|a| v.attribute(*a)))
+        .or_else(|| find("Properties").and_then(|p| p.children().find(|n| n.tag_name().name() == "Logo")).and_then(|n| n.text()))
+        .unwrap_or("");
+
+    Some(XboxManifest {
+        identity_name: identity.and_then(|i| i.attribute("Name")).unwrap_or("").to_string(),
+        publisher: identity.and_then(|i| i.attribute("Publisher")).unwrap_or("").to_string(),
+        app_id: application.and_then(|a| a.attribute("Id")).unwrap_or("").to_string(),
+        title,
+        executable: application.and_then(|a| a.attribute("Executable")).unwrap_or("").to_string(),
+        logo: logo.trim().to_string(),
+    })
+}
+
+/// PackageFamilyName = <Identity Name>_<PublisherId>, où PublisherId est le SHA-256 du Publisher
+/// (UTF-16LE) tronqué à 64 bits puis encodé sur 13 caractères en base32 de Crockford.
+pub fn package_family_name(identity_name: &str, publisher: &str) -> String {
+    const ALPHABET: &[u8; 32] = b"0123456789abcdefghjkmnpqrstvwxyz";
+    let utf16: Vec<u8> = publisher.encode_utf16().flat_map(|u| u.to_le_bytes()).collect();
+    let hash = Sha256::digest(&utf16);
+    let mut first = [0u8; 8];
+    first.copy_from_slice(&hash[..8]);
+    // 64 bits + 1 bit de bourrage = 13 groupes de 5 bits
+    let bits = u128::from(u64::from_be_bytes(first)) << 1;
+    let publisher_id: String = (0..13)
+        .map(|i| ALPHABET[((bits >> (5 * (12 - i))) & 0x1F) as usize] as char)
+        .collect();
+    format!("{}_{}", identity_name, publisher_id)
+}
+
+// Les logos sont déclarés sans qualificatif ("Logo.png") mais livrés en "Logo.scale-200.png"...
+fn resolve_asset(content_dir: &Path, relative: &str) -> Option<String> {
+    if relative.is_empty() { return None; }
+    let declared = content_dir.join(relative.replace('\\', "/"));
+    if declared.is_file() {
+        return Some(declared.to_string_lossy().to_string());
+    }
+    let stem = declared.file_stem()?.to_string_lossy().to_lowercase();
+    let ext = declared.extension()?.to_string_lossy().to_lowercase();
+    let mut variants: Vec<PathBuf> = fs::read_dir(declared.parent()?)
+        .ok()?
+        .flatten()
+        .map(|e| e.path())
+        .filter(|p| {
+            let name = p.file_name().unwrap_or_default().to_string_lossy().to_lowercase();
+            name.starts_with(&format!("{}.", stem)) && name.ends_with(&format!(".{}", ext))
+        })
+        .collect();
+    // Par ordre alphabétique, la dernière variante est la plus grande échelle (scale-400 > scale-100)
+    variants.sort();
+    variants.last().map(|p| p.to_string_lossy().to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MICROSOFT_GAME_CONFIG: &str = r#"<?xml version="1.0" encoding="utf-8"?>
+<Game configVersion="1">
+  <Identity Name="Microsoft.624F8B84B80" Publisher="CN=A4954634-DF4B-47C7-AB70-D3215D246AF1" Version="1.0.2.0"/>
+  <ExecutableList>
+    <Executable Name="Console\Game.exe" TargetDeviceFamily="Xbox" Id="Game"/>
+    <Executable Name="gamelaunchhelper.exe" TargetDeviceFamily="PC" Id="Game"/>
+  </ExecutableList>
+  <ShellVisuals DefaultDisplayName="Forza Horizon 5" PublisherDisplayName="Xbox Game Studios"
+                StoreLogo="StoreLogo.png" Square150x150Logo="Logo.png" Square44x44Logo="SmallLogo.png"/>
+</Game>"#;
+
+    const APPX_MANIFEST: &str = r#"<?xml version="1.0" encoding="utf-8"?>
+<Package xmlns="http://schemas.microsoft.com/appx/manifest/foundation/windows10"
+         xmlns:uap="http://schemas.microsoft.com/appx/manifest/uap/windows10">
+  <Identity Name="Microsoft.624F8B84B80" Publisher="CN=A4954634-DF4B-47C7-AB70-D3215D246AF1" Version="1.0.2.0" ProcessorArchitecture="x64"/>
+  <Properties>
+    <DisplayName>ms-resource:ApplicationDisplayName</DisplayName>
+    <Logo>StoreLogo.png</Logo>
+  </Properties>
+  <Applications>
+    <Application Id="App" Executable="gamelaunchhelper.exe" EntryPoint="Windows.FullTrustApplication">
+      <uap:VisualElements DisplayName="ms-resource:ApplicationDisplayName" Square150x150Logo="Logo.png" Square44x44Logo="SmallLogo.png"/>
+    </Application>
+  </Applications>
+</Package>"#;
+
+    #[test]
+    fn parses_microsoft_game_config() {
+        let manifest = parse_microsoft_game_config(MICROSOFT_GAME_CONFIG).unwrap();
+        assert_eq!(manifest.identity_name, "Microsoft.624F8B84B80");
+        assert_eq!(manifest.publisher, "CN=A4954634-DF4B-47C7-AB70-D3215D246AF1");
+        // L'exécutable Xbox est ignoré au profit de celui destiné au PC
+        assert_eq!(manifest.executable, "gamelaunchhelper.exe");
+        assert_eq!(manifest.app_id, "Game");
+        assert_eq!(manifest.title, "Forza Horizon 5");
+        assert_eq!(manifest.logo, "Logo.png");
+        assert_eq!(parse_microsoft_game_config(APPX_MANIFEST), None);
+    }
+
+    #[test]
+    fn parses_appx_manifest_without_resource_names() {
+        let manifest = parse_appx_manifest(APPX_MANIFEST).unwrap();
+        assert_eq!(manifest.identity_name, "Microsoft.624F8B84B80");
+        assert_eq!(manifest.app_id, "App");
+        assert_eq!(manifest.executable, "gamelaunchhelper.exe");
+        assert_eq!(manifest.title, "");
+        assert_eq!(manifest.logo, "Logo.png");
+        assert_eq!(parse_appx_manifest(MICROSOFT_GAME_CONFIG), None);
+        assert_eq!(parse_appx_manifest("<Package>"), None);
+    }
+
+    #[test]
+    fn merges_identity_from_appx_and_display_from_config() {
+        let config = parse_microsoft_game_config(MICROSOFT_GAME_CONFIG);
+        let appx = parse_appx_manifest(APPX_MANIFEST);
+        let merged = merge_manifests(config.clone(), appx.clone()).unwrap();
+        assert_eq!(merged.app_id, "App");
+        assert_eq!(merged.title, "Forza Horizon 5");
+        assert_eq!(merged.executable, "gamelaunchhelper.exe");
+        assert_eq!(merge_manifests(config.clone(), None), config);
+        assert_eq!(merge_manifests(None, appx.clone()), appx);
+        assert_eq!(merge_manifests(None, None), None);
+    }
+
+    #[test]
+    fn computes_the_package_family_name() {
+        // Famille de la Calculatrice Windows : Microsoft.WindowsCalculator_8wekyb3d8bbwe
+        assert_eq!(
+            package_family_name(
+                "Microsoft.WindowsCalculator",
+                "CN=Microsoft Corporation, O=Microsoft Corporation, L=Redmond, S=Washington, C=US"
+            ),
+            "Microsoft.WindowsCalculator_8wekyb3d8bbwe"
+        );
+    }
+}
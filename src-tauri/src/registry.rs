@@ -0,0 +1,431 @@
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::Path;
+use std::sync::Arc;
+
+// ===================== ACCÈS AU REGISTRE WINDOWS =====================
+// Les scanners ne parlent jamais directement à winreg : ils passent par `RegistryReader`.
+// Sous Windows on lit le vrai registre, ailleurs (Linux, CI, tests) on utilise un
+// `MemoryRegistry` rempli à la main, depuis un export .reg ou depuis un JSON.
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Hive {
+    CurrentUser,
+    LocalMachine,
+}
+
+impl Hive {
+    pub fn name(&self) -> &'static str {
+        match self {
+            Hive::CurrentUser => "HKEY_CURRENT_USER",
+            Hive::LocalMachine => "HKEY_LOCAL_MACHINE",
+        }
+    }
+}
+
+/// Lecture seule du registre. Les chemins utilisent des `\` (ex: "SOFTWARE\\GOG.com\\Games").
+pub trait RegistryReader: Send + Sync {
+    /// Noms des sous-clés directes de `path`. Vide si la clé n'existe pas.
+    fn subkeys(&self, hive: Hive, path: &str) -> Vec<String>;
+
+    /// Valeur texte (les DWORD sont renvoyés en décimal). `None` si absente.
+    fn value(&self, hive: Hive, path: &str, name: &str) -> Option<String>;
+}
+
+/// Registre utilisé par défaut sur la plateforme courante.
+pub fn default_registry() -> Arc<dyn RegistryReader> {
+    #[cfg(windows)]
+    {
+        Arc::new(WindowsRegistry)
+    }
+    #[cfg(not(windows))]
+    {
+        Arc::new(MemoryRegistry::default())
+    }
+}
+
+// ===================== BACKEND WINREG =====================
+
+#[cfg(windows)]
+pub struct WindowsRegistry;
+
+#[cfg(windows)]
+impl WindowsRegistry {
+    fn open(hive: Hive, path: &str) -> Option<winreg::RegKey> {
+        use winreg::enums::*;
+        let root = match hive {
+            Hive::CurrentUser => winreg::RegKey::predef(HKEY_CURRENT_USER),
+            Hive::LocalMachine => winreg::RegKey::predef(HKEY_LOCAL_MACHINE),
+        };
+        root.open_subkey(path).ok()
+    }
+}
+
+#[cfg(windows)]
+impl RegistryReader for WindowsRegistry {
+    fn subkeys(&self, hive: Hive, path: &str) -> Vec<String> {
+        match Self::open(hive, path) {
+            Some(key) => key.enum_keys().flatten().filter(|k| !k.is_empty()).collect(),
+            None => Vec::new(),
+        }
+    }
+
+    fn value(&self, hive: Hive, path: &str, name: &str) -> Option<String> {
+        let key = Self::open(hive, path)?;
+        if let Ok(text) = key.get_value::<String, _>(name) {
+            return Some(text);
+        }
+        key.get_value::<u32, _>(name).ok().map(|n| n.to_string())
+    }
+}
+
+// ===================== BACKEND MÉMOIRE (FIXTURES) =====================
+
+/// Registre en mémoire : chemin complet de la clé ("HKEY_LOCAL_MACHINE\\SOFTWARE\\...") -> valeurs.
+/// Les comparaisons ignorent la casse, comme le vrai registre.
+#[derive(Default, Clone, Debug)]
+pub struct MemoryRegistry {
+    keys: BTreeMap<String, BTreeMap<String, String>>,
+}
+
+impl MemoryRegistry {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Ajoute (ou remplace) une valeur. La clé et ses parents sont créés implicitement.
+    pub fn insert(&mut self, hive: Hive, path: &str, name: &str, value: &str) {
+        let full = Self::full_path(hive, path);
+        self.key_mut(&full).insert(name.to_string(), value.to_string());
+    }
+
+    /// Fixture JSON : `{ "HKLM\\SOFTWARE\\GOG.com\\Games\\1": { "gameName": "..." } }`.
+    pub fn from_json(json: &str) -> Result<Self, String> {
+        let raw: BTreeMap<String, BTreeMap<String, String>> =
+            serde_json::from_str(json).map_err(|e| e.to_string())?;
+        let mut registry = Self::new();
+        for (path, values) in raw {
+            let full = normalize_key_path(&path);
+            registry.key_mut(&full).extend(values);
+        }
+        Ok(registry)
+    }
+
+    /// Export texte de regedit ("Windows Registry Editor Version 5.00").
+    /// Seules les valeurs texte et DWORD sont conservées, les valeurs binaires sont ignorées.
+    pub fn from_reg_export(content: &str) -> Self {
+        let mut registry = Self::new();
+        let mut current: Option<String> = None;
+        let mut in_continuation = false;
+
+        for raw_line in content.lines() {
+            let line = raw_line.trim();
+            // Les valeurs hex(...) longues sont coupées en plusieurs lignes terminées par "\"
+            if in_continuation {
+                in_continuation = line.ends_with('\\');
+                continue;
+            }
+            if line.is_empty() || line.starts_with(';') || line.starts_with("Windows Registry Editor") || line == "REGEDIT4" {
+                continue;
+            }
+            if line.starts_with('[') && line.ends_with(']') {
+                let path = &line[1..line.len() - 1];
+                // "[-HKEY_...]" supprime une clé : rien à importer
+                if path.starts_with('-') {
+                    current = None;
+                } else {
+                    let full = normalize_key_path(path);
+                    registry.key_mut(&full);
+                    current = Some(full);
+                }
+                continue;
+            }
+            if line.ends_with('\\') {
+                in_continuation = true;
+                continue;
+            }
+            let Some(key) = current.clone() else { continue };
+            if let Some((name, data)) = parse_reg_value_line(line) {
+                registry.key_mut(&key).insert(name, data);
+            }
+        }
+        registry
+    }
+
+    /// Lit un fichier .reg. regedit exporte en UTF-16 LE avec BOM, on gère aussi l'UTF-8.
+    pub fn from_reg_file(path: &Path) -> Option<Self> {
+        let bytes = fs::read(path).ok()?;
+        let content = if bytes.starts_with(&[0xFF, 0xFE]) {
+            let units: Vec<u16> = bytes[2..]
+                .chunks_exact(2)
+                .map(|c| u16::from_le_bytes([c[0], c[1]]))
+                .collect();
+            String::from_utf16_lossy(&units)
+        } else {
+            String::from_utf8_lossy(&bytes).trim_start_matches('\u{feff}').to_string()
+        };
+        Some(Self::from_reg_export(&content))
+    }
+
+    fn key_mut(&mut self, full_path: &str) -> &mut BTreeMap<String, String> {
+        let existing = self.keys.keys().find(|k| k.eq_ignore_ascii_case(full_path)).cloned();
+        self.keys.entry(existing.unwrap_or_else(|| full_path.to_string())).or_default()
+    }
+
+    fn full_path(hive: Hive, path: &str) -> String {
+        let path = path.trim_matches('\\');
+        if path.is_empty() { hive.name().to_string() } else { format!("{}\\{}", hive.name(), path) }
+    }
+}
+
+impl RegistryReader for MemoryRegistry {
+    fn subkeys(&self, hive: Hive, path: &str) -> Vec<String> {
+        let prefix = format!("{}\\", Self::full_path(hive, path));
+        let mut names: Vec<String> = Vec::new();
+        for key in self.keys.keys() {
+            if key.get(..prefix.len()).is_some_and(|p| p.eq_ignore_ascii_case(&prefix)) {
+                let child = key[prefix.len()..].split('\\').next().unwrap_or_default();
+                if !child.is_empty() && !names.iter().any(|n| n.eq_ignore_ascii_case(child)) {
+                    names.push(child.to_string());
+                }
+            }
+        }
+        names
+    }
+
+    fn value(&self, hive: Hive, path: &str, name: &str) -> Option<String> {
+        let full = Self::full_path(hive, path);
+        let (_, values) = self.keys.iter().find(|(k, _)| k.eq_ignore_ascii_case(&full))?;
+        values
+            .iter()
+            .find(|(n, _)| n.eq_ignore_ascii_case(name))
+            .map(|(_, v)| v.clone())
+    }
+}
+
+// Accepte les abréviations HKLM / HKCU dans les fixtures
+fn normalize_key_path(path: &str) -> String {
+    let path = path.trim().trim_matches('\\');
+    let (root, rest) = path.split_once('\\').unwrap_or((path, ""));
+    let root = match root.to_uppercase().as_str() {
+        "HKLM" | "HKEY_LOCAL_MACHINE" => Hive::LocalMachine.name(),
+        "HKCU" | "HKEY_CURRENT_USER" => Hive::CurrentUser.name(),
+        _ => root,
+    };
+    if rest.is_empty() { root.to_string() } else { format!("{}\\{}", root, rest) }
+}
+
+// "name"="valeur", @="défaut" ou "name"=dword:0000002a
+fn parse_reg_value_line(line: &str) -> Option<(String, String)> {
+    let (name, rest) = if let Some(rest) = line.strip_prefix('@') {
+        (String::new(), rest)
+    } else {
+        let (name, consumed) = parse_reg_string(line)?;
+        (name, &line[consumed..])
+    };
+    let data = rest.trim_start().strip_prefix('=')?.trim_start();
+
+    if data.starts_with('"') {
+        let (text, _) = parse_reg_string(data)?;
+        Some((name, text))
+    } else if let Some(hex) = data.strip_prefix("dword:") {
+        let n = u32::from_str_radix(hex.trim(), 16).ok()?;
+        Some((name, n.to_string()))
+    } else {
+        None
+    }
+}
+
+// Lit une chaîne entre guillemets avec les échappements \\ et \". Renvoie aussi le nombre d'octets consommés.
+fn parse_reg_string(input: &str) -> Option<(String, usize)> {
+    let mut chars = input.char_indices();
+    if chars.next()?.1 != '"' { return None; }
+    let mut out = String::new();
+    while let Some((i, c)) = chars.next() {
+        match c {
+            '"' => return Some((out, i + 1)),
+            '\\' => {
+                let (_, escaped) = chars.next()?;
+                out.push(escaped);
+            }
+            _ => out.push(c),
+        }
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+
+    use crate::providers::steam::find_steam_roots;
+    use crate::providers::{EaProvider, GameProvider, GogProvider, UbisoftProvider};
+
+    const EXPORT: &str = r#"Windows Registry Editor Version 5.00
+
+; Jeu GOG (vue 32 bits)
+[HKEY_LOCAL_MACHINE\SOFTWARE\WOW6432Node\GOG.com\Games\1207658924]
+"gameName"="The Witcher \"Enhanced\""
+"path"="C:\\GOG Games\\The Witcher"
+"exe"="System\\witcher.exe"
+"dependsOn"=dword:0000002a
+"binary"=hex:01,02,\
+  03,04
+
+[HKEY_LOCAL_MACHINE\SOFTWARE\Ubisoft\Launcher\Installs\4311]
+"InstallDir"="D:/Games/Far Cry 5/"
+
+[HKEY_CURRENT_USER\Software\Valve\Steam]
+@="défaut"
+"SteamPath"="c:/program files (x86)/steam"
+"#;
+
+    #[test]
+    fn reg_export_unescapes_strings_and_reads_dwords() {
+        let registry = MemoryRegistry::from_reg_export(EXPORT);
+        let key = "SOFTWARE\\WOW6432Node\\GOG.com\\Games\\1207658924";
+        assert_eq!(registry.value(Hive::LocalMachine, key, "gameName").as_deref(), Some("The Witcher \"Enhanced\""));
+        assert_eq!(registry.value(Hive::LocalMachine, key, "path").as_deref(), Some("C:\\GOG Games\\The Witcher"));
+        assert_eq!(registry.value(Hive::LocalMachine, key, "exe").as_deref(), Some("System\\witcher.exe"));
+        assert_eq!(registry.value(Hive::LocalMachine, key, "dependsOn").as_deref(), Some("42"));
+        // Les valeurs binaires (et leurs lignes de continuation) sont ignorées
+        assert_eq!(registry.value(Hive::LocalMachine, key, "binary"), None);
+    }
+
+    #[test]
+    fn reg_export_keeps_hives_apart_and_ignores_case() {
+        let registry = MemoryRegistry::from_reg_export(EXPORT);
+        assert_eq!(registry.value(Hive::CurrentUser, "software\\valve\\steam", "steampath").as_deref(), Some("c:/program files (x86)/steam"));
+        assert_eq!(registry.value(Hive::CurrentUser, "Software\\Valve\\Steam", "").as_deref(), Some("défaut"));
+        assert_eq!(registry.value(Hive::LocalMachine, "Software\\Valve\\Steam", "SteamPath"), None);
+
+        assert_eq!(registry.subkeys(Hive::LocalMachine, "SOFTWARE\\WOW6432Node\\GOG.com\\Games"), vec!["1207658924"]);
+        let mut software = registry.subkeys(Hive::LocalMachine, "SOFTWARE");
+        software.sort();
+        assert_eq!(software, vec!["Ubisoft", "WOW6432Node"]);
+    }
+
+    #[test]
+    fn json_fixture_accepts_abbreviated_hives() {
+        let registry = MemoryRegistry::from_json(
+            r#"{
+                "HKLM\\SOFTWARE\\GOG.com\\Games\\1": { "gameName": "Gwent" },
+                "HKEY_CURRENT_USER\\Software\\Epic": { "Path": "E:\\Epic" }
+            }"#,
+        )
+        .unwrap();
+        assert_eq!(registry.value(Hive::LocalMachine, "SOFTWARE\\GOG.com\\Games\\1", "gameName").as_deref(), Some("Gwent"));
+        assert_eq!(registry.value(Hive::CurrentUser, "Software\\Epic", "Path").as_deref(), Some("E:\\Epic"));
+        assert!(MemoryRegistry::from_json("{ pas du json").is_err());
+    }
+
+    #[test]
+    fn gog_provider_reads_the_memory_registry() {
+        let install_dir = std::env::temp_dir().join(format!("geewers-registry-gog-{}", std::process::id()));
+        fs::create_dir_all(&install_dir).unwrap();
+
+        let mut registry = MemoryRegistry::new();
+        let key = "SOFTWARE\\WOW6432Node\\GOG.com\\Games\\1207658924";
+        registry.insert(Hive::LocalMachine, key, "gameName", "The Witcher");
+        registry.insert(Hive::LocalMachine, key, "path", &install_dir.to_string_lossy());
+        registry.insert(Hive::LocalMachine, key, "exe", "witcher.exe");
+        // Dossier absent : le jeu n'est pas proposé
+        registry.insert(Hive::LocalMachine, "SOFTWARE\\GOG.com\\Games\\2", "gameName", "Désinstallé");
+        registry.insert(Hive::LocalMachine, "SOFTWARE\\GOG.com\\Games\\2", "path", "/chemin/inexistant");
+
+        let games = GogProvider::new(Arc::new(registry)).scan();
+        fs::remove_dir_all(&install_dir).ok();
+
+        assert_eq!(games.len(), 1);
+        assert_eq!(games[0].id, "1207658924");
+        assert_eq!(games[0].title, "The Witcher");
+        assert_eq!(games[0].platform, "GOG");
+        assert_eq!(games[0].exe_path, install_dir.join("witcher.exe").to_string_lossy());
+    }
+
+    fn temp_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("geewers-registry-{}-{}", name, std::process::id()));
+        fs::remove_dir_all(&dir).ok();
+        fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    #[test]
+    fn steam_roots_come_from_steam_path_then_install_path() {
+        let root = temp_dir("steam");
+        let user_root = root.join("Steam");
+        let machine_root = root.join("Program Files (x86)").join("Steam");
+        fs::create_dir_all(user_root.join("steamapps")).unwrap();
+        fs::create_dir_all(machine_root.join("steamapps")).unwrap();
+
+        let mut registry = MemoryRegistry::new();
+        registry.insert(Hive::LocalMachine, "SOFTWARE\\WOW6432Node\\Valve\\Steam", "InstallPath", &machine_root.to_string_lossy());
+        let roots = find_steam_roots(&registry);
+        assert_eq!(roots.first(), Some(&machine_root));
+
+        // SteamPath de l'utilisateur en premier ; un même dossier déclaré deux fois n'est gardé qu'une fois
+        registry.insert(Hive::CurrentUser, "Software\\Valve\\Steam", "SteamPath", &user_root.to_string_lossy());
+        registry.insert(Hive::LocalMachine, "SOFTWARE\\Valve\\Steam", "InstallPath", &user_root.to_string_lossy());
+        let roots = find_steam_roots(&registry);
+        assert_eq!(roots[..2], [user_root.clone(), machine_root.clone()]);
+        assert_eq!(roots.iter().filter(|r| **r == user_root).count(), 1);
+
+        // Dossier sans steamapps : ignoré
+        let mut registry = MemoryRegistry::new();
+        registry.insert(Hive::CurrentUser, "Software\\Valve\\Steam", "SteamPath", &root.to_string_lossy());
+        assert!(!find_steam_roots(&registry).contains(&root));
+        fs::remove_dir_all(&root).ok();
+    }
+
+    #[test]
+    fn ea_provider_walks_the_uninstall_keys() {
+        let root = temp_dir("ea");
+        let game_dir = root.join("Battlefield 1");
+        fs::create_dir_all(game_dir.join("__Installer")).unwrap();
+        fs::write(
+            game_dir.join("__Installer").join("installerdata.xml"),
+            r#"<DiPManifest version="4.0"><contentIDs><contentID>1035052</contentID></contentIDs><gameTitles><gameTitle locale="en_US">Battlefield 1</gameTitle></gameTitles><runtime><launcher><filePath>bf1.exe</filePath><trial>0</trial></launcher></runtime></DiPManifest>"#,
+        )
+        .unwrap();
+        // Entrée de désinstallation sans manifeste : ce n'est pas un jeu EA
+        let other_dir = root.join("Autre");
+        fs::create_dir_all(&other_dir).unwrap();
+
+        let mut registry = MemoryRegistry::new();
+        let uninstall = "SOFTWARE\\WOW6432Node\\Microsoft\\Windows\\CurrentVersion\\Uninstall";
+        registry.insert(Hive::LocalMachine, &format!("{}\\{{BF1}}", uninstall), "InstallLocation", &format!("{}\\", game_dir.to_string_lossy()));
+        registry.insert(Hive::LocalMachine, &format!("{}\\Autre", uninstall), "InstallLocation", &other_dir.to_string_lossy());
+
+        let games = EaProvider::with_install_data(Arc::new(registry), root.join("InstallData")).scan();
+        fs::remove_dir_all(&root).ok();
+
+        assert_eq!(games.len(), 1);
+        assert_eq!(games[0].id, "1035052");
+        assert_eq!(games[0].title, "Battlefield 1");
+        assert_eq!(games[0].exe_path, game_dir.join("bf1.exe").to_string_lossy());
+    }
+
+    #[test]
+    fn ubisoft_provider_reads_install_dirs() {
+        let root = temp_dir("ubisoft");
+        let game_dir = root.join("Far Cry 5");
+        fs::create_dir_all(&game_dir).unwrap();
+
+        let mut registry = MemoryRegistry::new();
+        let installs = "SOFTWARE\\WOW6432Node\\Ubisoft\\Launcher\\Installs";
+        registry.insert(Hive::LocalMachine, &format!("{}\\4311", installs), "InstallDir", &game_dir.to_string_lossy());
+        registry.insert(Hive::LocalMachine, &format!("{}\\635", installs), "InstallDir", "/chemin/inexistant");
+        registry.insert(Hive::LocalMachine, &format!("{}\\720", installs), "Language", "fr-FR");
+
+        // Sans cache de configurations, le titre vient du nom du dossier
+        let games = UbisoftProvider::with_launcher_dir(Arc::new(registry), root.join("launcher")).scan();
+        fs::remove_dir_all(&root).ok();
+
+        assert_eq!(games.len(), 1);
+        assert_eq!(games[0].id, "4311");
+        assert_eq!(games[0].title, "Far Cry 5");
+        assert_eq!(games[0].platform, "Ubisoft");
+        assert_eq!(games[0].install_dir, game_dir.to_string_lossy());
+    }
+}
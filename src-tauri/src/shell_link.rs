@@ -0,0 +1,381 @@
+use std::path::Path;
+
+// ===================== RACCOURCIS WINDOWS (.lnk / .url) =====================
+// .lnk : format binaire "Shell Link" ([MS-SHLLINK]) :
+//   en-tête (0x4C octets, dont LinkFlags et IconIndex)
+//   [LinkTargetIDList] si HasLinkTargetIDList : sauté, on ne résout pas les listes d'ID du shell
+//   [LinkInfo] si HasLinkInfo : chemin local (LocalBasePath + CommonPathSuffix) ou réseau
+//   StringData : NAME, RELATIVE_PATH, WORKING_DIR, ARGUMENTS, ICON_LOCATION (selon les flags)
+//   ExtraData : blocs, dont EnvironmentVariableDataBlock (cible avec %VARIABLES%)
+// .url : fichier INI, section [InternetShortcut] avec URL=, IconFile=, IconIndex=.
+// Le parsing est en pur Rust pour fonctionner (et se tester) hors de Windows.
+
+const HEADER_SIZE: u32 = 0x4C;
+
+const HAS_LINK_TARGET_ID_LIST: u32 = 0x0000_0001;
+const HAS_LINK_INFO: u32 = 0x0000_0002;
+const HAS_NAME: u32 = 0x0000_0004;
+const HAS_RELATIVE_PATH: u32 = 0x0000_0008;
+const HAS_WORKING_DIR: u32 = 0x0000_0010;
+const HAS_ARGUMENTS: u32 = 0x0000_0020;
+const HAS_ICON_LOCATION: u32 = 0x0000_0040;
+const IS_UNICODE: u32 = 0x0000_0080;
+
+const VOLUME_ID_AND_LOCAL_BASE_PATH: u32 = 0x1;
+const COMMON_NETWORK_RELATIVE_LINK_AND_PATH_SUFFIX: u32 = 0x2;
+
+const ENVIRONMENT_VARIABLE_BLOCK: u32 = 0xA000_0001;
+
+/// Contenu utile d'un fichier .lnk.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct ShellLink {
+    /// Cible résolue (LinkInfo, sinon bloc de variables d'environnement). Vide pour les raccourcis
+    /// "publiés" par Windows Installer, qui n'ont qu'une liste d'ID.
+    pub target: String,
+    pub relative_path: String,
+    pub working_dir: String,
+    pub arguments: String,
+    pub description: String,
+    pub icon_location: String,
+    pub icon_index: i32,
+}
+
+/// Contenu utile d'un fichier .url.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct InternetShortcut {
+    pub url: String,
+    pub icon_file: String,
+    pub icon_index: i32,
+}
+
+/// Parse un fichier .lnk.
+pub fn parse_lnk(data: &[u8]) -> Result<ShellLink, String> {
+    let mut reader = LnkReader { data, pos: 0 };
+    if reader.u32()? != HEADER_SIZE {
+        return Err(".lnk: en-tête invalide".to_string());
+    }
+    reader.pos = 0x14;
+    let flags = reader.u32()?;
+    reader.pos = 0x38;
+    let icon_index = reader.u32()? as i32;
+    reader.pos = HEADER_SIZE as usize;
+
+    if flags & HAS_LINK_TARGET_ID_LIST != 0 {
+        let size = reader.u16()? as usize;
+        reader.skip(size)?;
+    }
+
+    let mut link = ShellLink { icon_index, ..Default::default() };
+    if flags & HAS_LINK_INFO != 0 {
+        let start = reader.pos;
+        let size = reader.u32()? as usize;
+        link.target = parse_link_info(reader.slice(start, size)?).unwrap_or_default();
+        reader.pos = start + size;
+    }
+
+    let unicode = flags & IS_UNICODE != 0;
+    let mut string_data = |flag: u32| -> Result<String, String> {
+        if flags & flag == 0 { return Ok(String::new()); }
+        reader.counted_string(unicode)
+    };
+    link.description = string_data(HAS_NAME)?;
+    link.relative_path = string_data(HAS_RELATIVE_PATH)?;
+    link.working_dir = string_data(HAS_WORKING_DIR)?;
+    link.arguments = string_data(HAS_ARGUMENTS)?;
+    link.icon_location = string_data(HAS_ICON_LOCATION)?;
+
+    // Blocs supplémentaires : on ne lit que la cible avec variables d'environnement
+    if link.target.is_empty() {
+        while let Ok(block_size) = reader.u32() {
+            if block_size < 8 { break; }
+            let start = reader.pos - 4;
+            let signature = reader.u32()?;
+            if signature == ENVIRONMENT_VARIABLE_BLOCK && block_size >= 0x314 {
+                let Ok(block) = reader.slice(start, block_size as usize) else { break };
+                let unicode_target = utf16_cstring(&block[0x10C..0x314]);
+                link.target = if unicode_target.is_empty() { ansi_cstring(&block[0x08..0x10C]) } else { unicode_target };
+                break;
+            }
+            reader.pos = start + block_size as usize;
+        }
+    }
+    Ok(link)
+}
+
+// LinkInfo : chemin local ou partage réseau, suivi du suffixe commun.
+// Les offsets Unicode n'existent que si l'en-tête fait au moins 0x24 octets.
+fn parse_link_info(info: &[u8]) -> Option<String> {
+    let field = |offset: usize| info.get(offset..offset + 4).map(|b| u32::from_le_bytes([b[0], b[1], b[2], b[3]]) as usize);
+    let has_unicode = field(4)? >= 0x24;
+    let info_flags = field(8)? as u32;
+    let string_at = |ansi_field: usize, unicode_field: usize| -> Option<String> {
+        match has_unicode.then(|| field(unicode_field)).flatten() {
+            Some(offset) if offset > 0 => Some(utf16_cstring(info.get(offset..)?)),
+            _ => Some(ansi_cstring(info.get(field(ansi_field)?..)?)),
+        }
+    };
+    let suffix = string_at(0x18, 0x20)?;
+
+    if info_flags & VOLUME_ID_AND_LOCAL_BASE_PATH != 0 {
+        let base = string_at(0x10, 0x1C)?;
+        return Some(join_windows_path(&base, &suffix));
+    }
+    if info_flags & COMMON_NETWORK_RELATIVE_LINK_AND_PATH_SUFFIX != 0 {
+        let network = info.get(field(0x14)?..)?;
+        let net_name_offset = u32::from_le_bytes(network.get(8..12)?.try_into().ok()?) as usize;
+        let net_name = ansi_cstring(network.get(net_name_offset..)?);
+        return Some(join_windows_path(&net_name, &suffix));
+    }
+    None
+}
+
+fn join_windows_path(base: &str, suffix: &str) -> String {
+    if suffix.is_empty() || base.ends_with('\\') {
+        format!("{}{}", base, suffix)
+    } else {
+        format!("{}\\{}", base, suffix)
+    }
+}
+
+fn ansi_cstring(bytes: &[u8]) -> String {
+    let end = bytes.iter().position(|&b| b == 0).unwrap_or(bytes.len());
+    String::from_utf8_lossy(&bytes[..end]).into_owned()
+}
+
+fn utf16_cstring(bytes: &[u8]) -> String {
+    let units: Vec<u16> = bytes
+        .chunks_exact(2)
+        .map(|c| u16::from_le_bytes([c[0], c[1]]))
+        .take_while(|&u| u != 0)
+        .collect();
+    String::from_utf16_lossy(&units)
+}
+
+struct LnkReader<'a> {
+    data: &'a [u8],
+    pos: usize,
+}
+
+impl<'a> LnkReader<'a> {
+    fn slice(&self, start: usize, len: usize) -> Result<&'a [u8], String> {
+        self.data.get(start..start.saturating_add(len)).ok_or_else(|| ".lnk: fin de données inattendue".to_string())
+    }
+
+    fn skip(&mut self, len: usize) -> Result<(), String> {
+        self.slice(self.pos, len)?;
+        self.pos += len;
+        Ok(())
+    }
+
+    fn u16(&mut self) -> Result<u16, String> {
+        let b = self.slice(self.pos, 2)?;
+        self.pos += 2;
+        Ok(u16::from_le_bytes([b[0], b[1]]))
+    }
+
+    fn u32(&mut self) -> Result<u32, String> {
+        let b = self.slice(self.pos, 4)?;
+        self.pos += 4;
+        Ok(u32::from_le_bytes([b[0], b[1], b[2], b[3]]))
+    }
+
+    // StringData : nombre de caractères (u16) puis les caractères, sans \0
+    fn counted_string(&mut self, unicode: bool) -> Result<String, String> {
+        let count = self.u16()? as usize;
+        let len = if unicode { count * 2 } else { count };
+        let bytes = self.slice(self.pos, len)?;
+        self.pos += len;
+        Ok(if unicode {
+            let units: Vec<u16> = bytes.chunks_exact(2).map(|c| u16::from_le_bytes([c[0], c[1]])).collect();
+            String::from_utf16_lossy(&units)
+        } else {
+            String::from_utf8_lossy(bytes).into_owned()
+        })
+    }
+}
+
+/// Parse un fichier .url (section [InternetShortcut]).
+pub fn parse_url_shortcut(content: &str) -> Option<InternetShortcut> {
+    let mut shortcut = InternetShortcut::default();
+    let mut in_section = false;
+    for line in content.lines() {
+        let line = line.trim();
+        if line.starts_with('[') {
+            in_section = line.eq_ignore_ascii_case("[InternetShortcut]");
+            continue;
+        }
+        if !in_section { continue; }
+        let Some((key, value)) = line.split_once('=') else { continue };
+        match key.trim().to_ascii_lowercase().as_str() {
+            "url" => shortcut.url = value.trim().to_string(),
+            "iconfile" => shortcut.icon_file = value.trim().to_string(),
+            "iconindex" => shortcut.icon_index = value.trim().parse().unwrap_or(0),
+            _ => {}
+        }
+    }
+    if shortcut.url.is_empty() { None } else { Some(shortcut) }
+}
+
+/// Remplace les %VARIABLES% connues de l'environnement ; les autres sont laissées telles quelles.
+pub fn expand_env_vars(value: &str) -> String {
+    let mut out = String::new();
+    let mut rest = value;
+    while let Some(start) = rest.find('%') {
+        out.push_str(&rest[..start]);
+        let after = &rest[start + 1..];
+        match after.find('%') {
+            Some(end) => {
+                let name = &after[..end];
+                match std::env::var(name).ok().filter(|_| !name.is_empty()) {
+                    Some(expanded) => out.push_str(&expanded),
+                    None => out.push_str(&rest[start..start + end + 2]),
+                }
+                rest = &after[end + 1..];
+            }
+            None => {
+                out.push_str(&rest[start..]);
+                rest = "";
+            }
+        }
+    }
+    out.push_str(rest);
+    out
+}
+
+/// Résout un chemin relatif de .lnk (ex: "..\\Jeux\\game.exe") depuis le dossier du raccourci.
+pub fn resolve_relative(lnk_path: &Path, relative: &str) -> String {
+    let Some(dir) = lnk_path.parent() else { return relative.to_string() };
+    let mut resolved = dir.to_path_buf();
+    for part in relative.split(['\\', '/']) {
+        match part {
+            "" | "." => {}
+            ".." => {
+                resolved.pop();
+            }
+            other => resolved.push(other),
+        }
+    }
+    resolved.to_string_lossy().to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn header(flags: u32, icon_index: i32) -> Vec<u8> {
+        let mut data = vec![0u8; HEADER_SIZE as usize];
+        data[..4].copy_from_slice(&HEADER_SIZE.to_le_bytes());
+        data[0x14..0x18].copy_from_slice(&flags.to_le_bytes());
+        data[0x38..0x3C].copy_from_slice(&icon_index.to_le_bytes());
+        data
+    }
+
+    fn unicode_string(data: &mut Vec<u8>, value: &str) {
+        let units: Vec<u16> = value.encode_utf16().collect();
+        data.extend((units.len() as u16).to_le_bytes());
+        data.extend(units.iter().flat_map(|u| u.to_le_bytes()));
+    }
+
+    // LinkInfo avec en-tête de 0x1C octets : VolumeID minimal, LocalBasePath et CommonPathSuffix ANSI
+    fn local_link_info(base: &str, suffix: &str) -> Vec<u8> {
+        let volume_id = [0x10, 0, 0, 0, 3, 0, 0, 0, 0, 0, 0, 0, 0x10, 0, 0, 0];
+        let volume_offset = 0x1C;
+        let base_offset = volume_offset + volume_id.len();
+        let suffix_offset = base_offset + base.len() + 1;
+        let size = suffix_offset + suffix.len() + 1;
+        let mut info = Vec::new();
+        for value in [size, 0x1C, VOLUME_ID_AND_LOCAL_BASE_PATH as usize, volume_offset, base_offset, 0, suffix_offset] {
+            info.extend((value as u32).to_le_bytes());
+        }
+        info.extend(volume_id);
+        info.extend(base.as_bytes());
+        info.push(0);
+        info.extend(suffix.as_bytes());
+        info.push(0);
+        info
+    }
+
+    #[test]
+    fn reads_link_info_and_unicode_string_data() {
+        let flags = HAS_LINK_INFO | HAS_RELATIVE_PATH | HAS_WORKING_DIR | HAS_ARGUMENTS | HAS_ICON_LOCATION | IS_UNICODE;
+        let mut data = header(flags, 2);
+        data.extend(local_link_info("C:\\Games\\Foo", "foo.exe"));
+        unicode_string(&mut data, "..\\Games\\Foo\\foo.exe");
+        unicode_string(&mut data, "C:\\Games\\Foo");
+        unicode_string(&mut data, "-windowed --lang=fr");
+        unicode_string(&mut data, "C:\\Games\\Foo\\icône.ico");
+        data.extend(0u32.to_le_bytes());
+
+        let link = parse_lnk(&data).unwrap();
+        assert_eq!(
+            link,
+            ShellLink {
+                target: "C:\\Games\\Foo\\foo.exe".to_string(),
+                relative_path: "..\\Games\\Foo\\foo.exe".to_string(),
+                working_dir: "C:\\Games\\Foo".to_string(),
+                arguments: "-windowed --lang=fr".to_string(),
+                description: "".to_string(),
+                icon_location: "C:\\Games\\Foo\\icône.ico".to_string(),
+                icon_index: 2,
+            }
+        );
+    }
+
+    #[test]
+    fn falls_back_to_the_environment_variable_block() {
+        // Raccourci sans LinkInfo, avec une liste d'ID sautée et un nom ANSI
+        let mut data = header(HAS_LINK_TARGET_ID_LIST | HAS_NAME, 0);
+        data.extend(4u16.to_le_bytes());
+        data.extend([2, 0, 0, 0]);
+        data.extend(4u16.to_le_bytes());
+        data.extend(b"Jeux");
+        // Bloc inconnu, sauté grâce à sa taille
+        data.extend(12u32.to_le_bytes());
+        data.extend(0xA000_0003u32.to_le_bytes());
+        data.extend([0; 4]);
+        let mut block = vec![0u8; 0x314];
+        block[..4].copy_from_slice(&0x314u32.to_le_bytes());
+        block[4..8].copy_from_slice(&ENVIRONMENT_VARIABLE_BLOCK.to_le_bytes());
+        let target = b"%ProgramFiles%\\Foo\\foo.exe";
+        block[8..8 + target.len()].copy_from_slice(target);
+        data.extend(&block);
+        data.extend(0u32.to_le_bytes());
+
+        let link = parse_lnk(&data).unwrap();
+        assert_eq!(link.target, "%ProgramFiles%\\Foo\\foo.exe");
+        assert_eq!(link.description, "Jeux");
+
+        // La cible Unicode du bloc est préférée à la cible ANSI
+        let offset = data.len() - 4 - 0x314 + 0x10C;
+        let unicode: Vec<u8> = "%SystemRoot%\\jeu.exe".encode_utf16().flat_map(u16::to_le_bytes).collect();
+        data[offset..offset + unicode.len()].copy_from_slice(&unicode);
+        assert_eq!(parse_lnk(&data).unwrap().target, "%SystemRoot%\\jeu.exe");
+    }
+
+    #[test]
+    fn rejects_truncated_or_foreign_files() {
+        let mut data = header(HAS_LINK_INFO | HAS_ARGUMENTS | IS_UNICODE, 0);
+        data.extend(local_link_info("C:\\Games", "bar.exe"));
+        unicode_string(&mut data, "-fullscreen");
+        assert!(parse_lnk(&data).is_ok());
+        for len in [0, 3, 0x20, HEADER_SIZE as usize, HEADER_SIZE as usize + 10, data.len() - 1] {
+            assert!(parse_lnk(&data[..len]).is_err(), "longueur {}", len);
+        }
+        assert!(parse_lnk(b"[InternetShortcut]\r\nURL=steam://rungameid/620\r\n").is_err());
+    }
+
+    #[test]
+    fn parses_internet_shortcuts() {
+        let content = "[{000214A0-0000-0000-C000-000000000046}]\r\nProp3=19,0\r\n[InternetShortcut]\r\nIDList=\r\nIconIndex=0\r\nURL=steam://rungameid/620\r\nIconFile=C:\\Program Files (x86)\\Steam\\steam\\games\\portal2.ico\r\n";
+        assert_eq!(
+            parse_url_shortcut(content),
+            Some(InternetShortcut {
+                url: "steam://rungameid/620".to_string(),
+                icon_file: "C:\\Program Files (x86)\\Steam\\steam\\games\\portal2.ico".to_string(),
+                icon_index: 0,
+            })
+        );
+        // URL hors de la section [InternetShortcut] : ignorée
+        assert_eq!(parse_url_shortcut("[Autre]\nURL=https://example.com\n"), None);
+    }
+}
@@ -0,0 +1,30 @@
+use std::fs;
+use std::path::Path;
+
+use crate::models::UserData;
+
+// ===================== GESTION SAUVEGARDE =====================
+
+pub fn load_data(path: &Path) -> UserData {
+    if path.exists() {
+        if let Ok(content) = fs::read_to_string(path) {
+            // On tente de charger. Si le champ 'theme' manque, serde utilise Default::default() grâce à l'attribut.
+            if let Ok(data) = serde_json::from_str::<UserData>(&content) {
+                return data;
+            }
+        }
+    }
+    UserData::default()
+}
+
+pub fn save_data(path: &Path, data: &UserData) {
+    // MODIF IMPORTANTE : On crée le dossier parent s'il n'existe pas
+    // C'est crucial pour le premier lancement dans AppData
+    if let Some(parent) = path.parent() {
+        let _ = fs::create_dir_all(parent);
+    }
+
+    if let Ok(json) = serde_json::to_string_pretty(data) {
+        let _ = fs::write(path, json);
+    }
+}
@@ -0,0 +1,172 @@
+use super::{KeyValues, VdfValue};
+
+// ===================== PARSEUR BINAIRE =====================
+// Format binaire des KeyValues (shortcuts.vdf, appinfo.vdf...) : chaque entrée commence
+// par un octet de type, suivi du nom (chaîne terminée par \0) puis de la valeur.
+// Les nombres sont convertis en texte décimal pour garder la même API que le format texte.
+
+const TYPE_OBJECT: u8 = 0x00;
+const TYPE_STRING: u8 = 0x01;
+const TYPE_INT32: u8 = 0x02;
+const TYPE_FLOAT32: u8 = 0x03;
+const TYPE_POINTER: u8 = 0x04;
+const TYPE_WIDESTRING: u8 = 0x05;
+const TYPE_COLOR: u8 = 0x06;
+const TYPE_UINT64: u8 = 0x07;
+const TYPE_END: u8 = 0x08;
+const TYPE_INT64: u8 = 0x0A;
+// Variante de fin d'objet rencontrée dans certains fichiers
+const TYPE_END_ALT: u8 = 0x0B;
+
+/// Parse un document binaire complet (ex: shortcuts.vdf).
+pub fn parse_binary(data: &[u8]) -> Result<KeyValues, String> {
+    let mut reader = BinaryReader::new(data, None);
+    reader.read_object()
+}
+
+/// Lecteur bas niveau, réutilisé par les formats qui embarquent des KeyValues binaires
+/// (appinfo.vdf). Depuis appinfo v29, les noms de clés sont des index dans une table de chaînes.
+pub struct BinaryReader<'a> {
+    data: &'a [u8],
+    pub pos: usize,
+    key_table: Option<&'a [String]>,
+}
+
+impl<'a> BinaryReader<'a> {
+    pub fn new(data: &'a [u8], key_table: Option<&'a [String]>) -> Self {
+        Self { data, pos: 0, key_table }
+    }
+
+    /// Lit des entrées jusqu'à la fin de l'objet courant (ou la fin des données).
+    pub fn read_object(&mut self) -> Result<KeyValues, String> {
+        let mut object = KeyValues::new();
+        loop {
+            let Some(kind) = self.read_u8() else { return Ok(object) };
+            if kind == TYPE_END || kind == TYPE_END_ALT {
+                return Ok(object);
+            }
+            let key = self.read_key()?;
+            let value = match kind {
+                TYPE_OBJECT => VdfValue::Obj(self.read_object()?),
+                TYPE_STRING => VdfValue::Str(self.read_cstring()?),
+                TYPE_WIDESTRING => VdfValue::Str(self.read_wide_cstring()?),
+                TYPE_INT32 | TYPE_POINTER | TYPE_COLOR => {
+                    VdfValue::Str(i32::from_le_bytes(self.take_array()?).to_string())
+                }
+                TYPE_FLOAT32 => VdfValue::Str(f32::from_le_bytes(self.take_array()?).to_string()),
+                TYPE_UINT64 => VdfValue::Str(u64::from_le_bytes(self.take_array()?).to_string()),
+                TYPE_INT64 => VdfValue::Str(i64::from_le_bytes(self.take_array()?).to_string()),
+                other => return Err(format!("VDF binaire: type inconnu 0x{:02x} à l'offset {}", other, self.pos - 1)),
+            };
+            object.push(key, value);
+        }
+    }
+
+    fn read_key(&mut self) -> Result<String, String> {
+        match self.key_table {
+            Some(table) => {
+                let index = u32::from_le_bytes(self.take_array()?) as usize;
+                table
+                    .get(index)
+                    .cloned()
+                    .ok_or_else(|| format!("VDF binaire: index de clé {} hors de la table", index))
+            }
+            None => self.read_cstring(),
+        }
+    }
+
+    pub fn read_u8(&mut self) -> Option<u8> {
+        let byte = *self.data.get(self.pos)?;
+        self.pos += 1;
+        Some(byte)
+    }
+
+    pub fn take(&mut self, len: usize) -> Result<&'a [u8], String> {
+        let end = self.pos.checked_add(len).filter(|&end| end <= self.data.len());
+        let Some(end) = end else { return Err("VDF binaire: fin de données inattendue".to_string()) };
+        let slice = &self.data[self.pos..end];
+        self.pos = end;
+        Ok(slice)
+    }
+
+    pub fn take_array<const N: usize>(&mut self) -> Result<[u8; N], String> {
+        let mut out = [0u8; N];
+        out.copy_from_slice(self.take(N)?);
+        Ok(out)
+    }
+
+    pub fn read_cstring(&mut self) -> Result<String, String> {
+        let rest = &self.data[self.pos.min(self.data.len())..];
+        let len = rest
+            .iter()
+            .position(|&b| b == 0)
+            .ok_or_else(|| "VDF binaire: chaîne non terminée".to_string())?;
+        let text = String::from_utf8_lossy(&rest[..len]).into_owned();
+        self.pos += len + 1;
+        Ok(text)
+    }
+
+    fn read_wide_cstring(&mut self) -> Result<String, String> {
+        let mut units = Vec::new();
+        loop {
+            let unit = u16::from_le_bytes(self.take_array()?);
+            if unit == 0 { break; }
+            units.push(unit);
+        }
+        Ok(String::from_utf16_lossy(&units))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reads_every_value_type() {
+        let mut data = vec![TYPE_OBJECT];
+        data.extend(b"root\0");
+        data.push(TYPE_STRING);
+        data.extend(b"name\0Jeu\0");
+        data.push(TYPE_INT32);
+        data.extend(b"signed\0");
+        data.extend((-2i32).to_le_bytes());
+        data.push(TYPE_UINT64);
+        data.extend(b"big\0");
+        data.extend(u64::MAX.to_le_bytes());
+        data.push(TYPE_WIDESTRING);
+        data.extend(b"wide\0");
+        data.extend("é!".encode_utf16().chain([0]).flat_map(u16::to_le_bytes));
+        data.extend([TYPE_END, TYPE_END]);
+
+        let root = parse_binary(&data).unwrap();
+        let obj = root.get_obj("root").unwrap();
+        assert_eq!(obj.get_str("name"), Some("Jeu"));
+        assert_eq!(obj.get_str("signed"), Some("-2"));
+        assert_eq!(obj.get_u64("big"), Some(u64::MAX));
+        assert_eq!(obj.get_str("wide"), Some("é!"));
+    }
+
+    #[test]
+    fn resolves_keys_through_the_string_table() {
+        let table = vec!["appid".to_string(), "name".to_string()];
+        let mut data = vec![TYPE_STRING];
+        data.extend(1u32.to_le_bytes());
+        data.extend(b"Portal\0");
+        data.push(TYPE_END);
+        let object = BinaryReader::new(&data, Some(&table)).read_object().unwrap();
+        assert_eq!(object.get_str("name"), Some("Portal"));
+
+        let mut data = vec![TYPE_STRING];
+        data.extend(7u32.to_le_bytes());
+        data.extend(b"x\0");
+        assert!(BinaryReader::new(&data, Some(&table)).read_object().is_err());
+    }
+
+    #[test]
+    fn rejects_unknown_types_and_truncated_values() {
+        assert!(parse_binary(b"\x09key\0").is_err());
+        assert!(parse_binary(b"\x01key\0sans fin").is_err());
+        assert!(parse_binary(b"\x02key\0\x01\x02").is_err());
+        assert!(parse_binary(b"\x05key\0\x41").is_err());
+    }
+}
@@ -0,0 +1,281 @@
+mod binary;
+
+pub use binary::{parse_binary, BinaryReader};
+
+// ===================== VALVE KEYVALUES (VDF) =====================
+// Format texte utilisé par Steam pour libraryfolders.vdf, les appmanifest_*.acf, localconfig.vdf...
+// (le format binaire de shortcuts.vdf / appinfo.vdf est lu par `binary.rs` dans les mêmes types)
+//
+//   "AppState"
+//   {
+//       "appid"     "620"
+//       "name"      "Portal 2"
+//   }
+//
+// Les clés ne sont pas uniques et ne tiennent pas compte de la casse : on garde donc
+// les entrées dans l'ordre du fichier et la recherche ignore la casse.
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum VdfValue {
+    Str(String),
+    Obj(KeyValues),
+}
+
+impl VdfValue {
+    pub fn as_str(&self) -> Option<&str> {
+        match self {
+            VdfValue::Str(s) => Some(s),
+            VdfValue::Obj(_) => None,
+        }
+    }
+
+    pub fn as_obj(&self) -> Option<&KeyValues> {
+        match self {
+            VdfValue::Obj(o) => Some(o),
+            VdfValue::Str(_) => None,
+        }
+    }
+}
+
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct KeyValues {
+    entries: Vec<(String, VdfValue)>,
+}
+
+impl KeyValues {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn push(&mut self, key: impl Into<String>, value: VdfValue) {
+        self.entries.push((key.into(), value));
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (&str, &VdfValue)> {
+        self.entries.iter().map(|(k, v)| (k.as_str(), v))
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Première valeur pour `key` (casse ignorée).
+    pub fn get(&self, key: &str) -> Option<&VdfValue> {
+        self.entries
+            .iter()
+            .find(|(k, _)| k.eq_ignore_ascii_case(key))
+            .map(|(_, v)| v)
+    }
+
+    pub fn get_str(&self, key: &str) -> Option<&str> {
+        self.get(key).and_then(VdfValue::as_str)
+    }
+
+    pub fn get_obj(&self, key: &str) -> Option<&KeyValues> {
+        self.get(key).and_then(VdfValue::as_obj)
+    }
+
+    /// Valeur numérique. Steam écrit tout en texte, les champs vides ou invalides donnent `None`.
+    pub fn get_u64(&self, key: &str) -> Option<u64> {
+        self.get_str(key).and_then(|s| s.trim().parse().ok())
+    }
+
+    /// Descend dans les sous-objets : `lookup(&["UserLocalConfigStore", "Software"])`.
+    pub fn lookup(&self, path: &[&str]) -> Option<&VdfValue> {
+        let (last, parents) = path.split_last()?;
+        let mut current = self;
+        for key in parents {
+            current = current.get_obj(key)?;
+        }
+        current.get(last)
+    }
+}
+
+// ===================== PARSEUR TEXTE =====================
+
+#[derive(Debug, PartialEq)]
+enum Token {
+    Text(String),
+    Open,
+    Close,
+}
+
+/// Parse un document VDF texte. Le résultat contient les entrées de premier niveau
+/// (en général une seule, ex: "AppState").
+pub fn parse(input: &str) -> Result<KeyValues, String> {
+    let tokens = tokenize(input)?;
+    let mut pos = 0;
+    let root = parse_object(&tokens, &mut pos, true)?;
+    Ok(root)
+}
+
+fn parse_object(tokens: &[Token], pos: &mut usize, top_level: bool) -> Result<KeyValues, String> {
+    let mut object = KeyValues::new();
+    loop {
+        let key = match tokens.get(*pos) {
+            None if top_level => return Ok(object),
+            None => return Err("VDF: '}' manquant en fin de fichier".to_string()),
+            Some(Token::Close) if !top_level => {
+                *pos += 1;
+                return Ok(object);
+            }
+            Some(Token::Close) => return Err("VDF: '}' inattendu".to_string()),
+            Some(Token::Open) => return Err("VDF: '{' sans clé".to_string()),
+            Some(Token::Text(key)) => key.clone(),
+        };
+        *pos += 1;
+
+        match tokens.get(*pos) {
+            Some(Token::Text(value)) => {
+                *pos += 1;
+                object.push(key, VdfValue::Str(value.clone()));
+            }
+            Some(Token::Open) => {
+                *pos += 1;
+                let child = parse_object(tokens, pos, false)?;
+                object.push(key, VdfValue::Obj(child));
+            }
+            _ => return Err(format!("VDF: valeur manquante pour la clé \"{}\"", key)),
+        }
+    }
+}
+
+fn tokenize(input: &str) -> Result<Vec<Token>, String> {
+    let mut tokens = Vec::new();
+    let mut chars = input.chars().peekable();
+
+    while let Some(&c) = chars.peek() {
+        match c {
+            c if c.is_whitespace() || c == '\u{feff}' => {
+                chars.next();
+            }
+            '{' => {
+                chars.next();
+                tokens.push(Token::Open);
+            }
+            '}' => {
+                chars.next();
+                tokens.push(Token::Close);
+            }
+            '/' => {
+                chars.next();
+                if chars.peek() == Some(&'/') {
+                    // Commentaire jusqu'à la fin de la ligne
+                    for c in chars.by_ref() {
+                        if c == '\n' { break; }
+                    }
+                } else {
+                    let mut text = String::from("/");
+                    read_bare(&mut chars, &mut text);
+                    tokens.push(Token::Text(text));
+                }
+            }
+            '[' => {
+                // Conditionnel de plateforme ([$WIN32], [!$X360]...) : ignoré
+                for c in chars.by_ref() {
+                    if c == ']' { break; }
+                }
+            }
+            '"' => {
+                chars.next();
+                let mut text = String::new();
+                let mut closed = false;
+                while let Some(c) = chars.next() {
+                    match c {
+                        '"' => {
+                            closed = true;
+                            break;
+                        }
+                        '\\' => match chars.next() {
+                            Some('n') => text.push('\n'),
+                            Some('t') => text.push('\t'),
+                            Some(other) => text.push(other),
+                            None => break,
+                        },
+                        _ => text.push(c),
+                    }
+                }
+                if !closed {
+                    return Err("VDF: chaîne non terminée".to_string());
+                }
+                tokens.push(Token::Text(text));
+            }
+            _ => {
+                let mut text = String::new();
+                read_bare(&mut chars, &mut text);
+                tokens.push(Token::Text(text));
+            }
+        }
+    }
+    Ok(tokens)
+}
+
+// Mot sans guillemets : s'arrête aux espaces, accolades et guillemets
+fn read_bare(chars: &mut std::iter::Peekable<std::str::Chars>, text: &mut String) {
+    while let Some(&c) = chars.peek() {
+        if c.is_whitespace() || c == '{' || c == '}' || c == '"' {
+            break;
+        }
+        text.push(c);
+        chars.next();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_nested_objects_in_file_order() {
+        let root = parse(
+            r#""libraryfolders"
+            {
+                "0"
+                {
+                    "path"  "C:\\Program Files (x86)\\Steam"
+                    "apps" { "620" "12345" "228980" "0" }
+                }
+                "1" { "path" "D:\\SteamLibrary" }
+            }"#,
+        )
+        .unwrap();
+        let folders = root.get_obj("libraryfolders").unwrap();
+        let keys: Vec<&str> = folders.iter().map(|(k, _)| k).collect();
+        assert_eq!(keys, vec!["0", "1"]);
+        assert_eq!(root.lookup(&["LibraryFolders", "0", "apps", "620"]).and_then(VdfValue::as_str), Some("12345"));
+        assert_eq!(folders.get_obj("1").unwrap().get_str("PATH"), Some("D:\\SteamLibrary"));
+    }
+
+    #[test]
+    fn unescapes_quotes_and_backslashes() {
+        let root = parse(r#""a" { "path" "C:\\Games\\Dir" "title" "Say \"hi\"" "lines" "one\ntwo" }"#).unwrap();
+        let a = root.get_obj("a").unwrap();
+        assert_eq!(a.get_str("path"), Some("C:\\Games\\Dir"));
+        assert_eq!(a.get_str("title"), Some("Say \"hi\""));
+        assert_eq!(a.get_str("lines"), Some("one\ntwo"));
+    }
+
+    #[test]
+    fn skips_comments_and_platform_conditionals() {
+        let root = parse(
+            "// en-tête\n\"root\"\n{\n  \"win\" \"1\" [$WIN32] // commentaire\n  \"x360\" \"0\" [!$X360]\n  bare value\n  \"url\" /path/to\n}\n",
+        )
+        .unwrap();
+        let obj = root.get_obj("root").unwrap();
+        assert_eq!(obj.get_str("win"), Some("1"));
+        assert_eq!(obj.get_str("x360"), Some("0"));
+        assert_eq!(obj.get_str("bare"), Some("value"));
+        assert_eq!(obj.get_str("url"), Some("/path/to"));
+        assert_eq!(obj.iter().count(), 4);
+    }
+
+    #[test]
+    fn rejects_malformed_documents() {
+        assert!(parse(r#""a" { "name" "pas fini"#).is_err());
+        assert!(tokenize("\"ouvert").is_err());
+        assert!(parse(r#""a" { "b" "c""#).is_err());
+        assert!(parse(r#""a" "b" }"#).is_err());
+        assert!(parse(r#""a" { "clé" }"#).is_err());
+        assert_eq!(parse("").unwrap(), KeyValues::new());
+    }
+}
@@ -0,0 +1,164 @@
+use regex::Regex;
+use serde_json::Value;
+
+use crate::models::{HltbTime, ModInfo};
+
+// ===================== OUTILS WEB =====================
+
+#[tauri::command]
+pub fn find_image_online(title: String) -> String {
+    let clean_title = title.replace("™", "").replace("®", "").replace("-", " ");
+    let encoded_title = urlencoding::encode(&clean_title);
+    let url = format!("https://store.steampowered.com/api/storesearch/?term={}&l=english&cc=US", encoded_title);
+    
+    if let Ok(response) = ureq::get(&url).call() {
+        if let Ok(json) = response.into_json::<Value>() {
+             if let Some(items) = json["items"].as_array() {
+                 if let Some(first_match) = items.first() {
+                     if let Some(steam_id) = first_match["id"].as_i64() {
+                         return format!("https://cdn.cloudflare.steamstatic.com/steam/apps/{}/library_600x900.jpg", steam_id);
+                     }
+                 }
+             }
+        }
+    }
+    "".to_string()
+}
+
+#[tauri::command]
+pub fn get_steam_details(steam_id: String, language: String) -> String {
+    // On utilise le paramètre 'language' reçu du frontend
+    let url = format!("https://store.steampowered.com/api/appdetails?appids={}&l={}", steam_id, language);
+    
+    if let Ok(response) = ureq::get(&url).call() {
+        if let Ok(json_str) = response.into_string() {
+            return json_str; 
+        }
+    }
+    "{}".to_string()
+}
+
+#[tauri::command]
+pub fn get_hltb(title: String) -> HltbTime {
+    let clean_title = title
+        .to_lowercase()
+        .replace("goty", "")
+        .replace("edition", "")
+        .replace(":", "")
+        .replace("-", " ")
+        .replace("™", "")
+        .replace("®", "")
+        .trim()
+        .to_string();
+
+    let url = "https://html.duckduckgo.com/html/";
+    let query = format!("site:howlongtobeat.com {}", clean_title);
+    
+    let agent = ureq::AgentBuilder::new()
+        .timeout(std::time::Duration::from_secs(5))
+        .build();
+
+    let response = agent.post(url)
+        .set("User-Agent", "Mozilla/5.0 (Windows NT 10.0; Win64; x64) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/120.0.0.0 Safari/537.36")
+        .set("Content-Type", "application/x-www-form-urlencoded")
+        .send_string(&format!("q={}", urlencoding::encode(&query)));
+
+    if let Ok(resp) = response {
+        if let Ok(html) = resp.into_string() {
+            let mut time = HltbTime::default();
+            let mut found = false;
+
+            if let Ok(re_main) = Regex::new(r"(?i)Main Story\s*[-:]?\s*(\d+)") {
+                if let Some(caps) = re_main.captures(&html) {
+                    if let Some(m) = caps.get(1) {
+                        time.main = m.as_str().parse().unwrap_or(0);
+                        found = true;
+                    }
+                }
+            }
+            if let Ok(re_extra) = Regex::new(r"(?i)Main \+ Extras\s*[-:]?\s*(\d+)") {
+                if let Some(caps) = re_extra.captures(&html) {
+                    if let Some(m) = caps.get(1) {
+                        time.main_extra = m.as_str().parse().unwrap_or(0);
+                        found = true;
+                    }
+                }
+            }
+            if let Ok(re_comp) = Regex::new(r"(?i)Completionist\s*[-:]?\s*(\d+)") {
+                if let Some(caps) = re_comp.captures(&html) {
+                    if let Some(m) = caps.get(1) {
+                        time.completionist = m.as_str().parse().unwrap_or(0);
+                        found = true;
+                    }
+                }
+            }
+
+            if found {
+                return time;
+            }
+        }
+    }
+    
+    HltbTime::default()
+}
+
+#[tauri::command]
+pub fn check_mod_support(title: String) -> ModInfo {
+    let mut info = ModInfo::default();
+    let clean_title_lower = title.to_lowercase();
+
+    // 1. Thunderstore
+    let ts_url = "https://thunderstore.io/api/experimental/community/";
+    if let Ok(response) = ureq::get(ts_url).call() {
+        if let Ok(json) = response.into_json::<Value>() {
+            if let Some(results) = json["results"].as_array() {
+                for community in results {
+                    let name = community["name"].as_str().unwrap_or("").to_lowercase();
+                    let identifier = community["identifier"].as_str().unwrap_or("");
+                    if name == clean_title_lower || clean_title_lower.contains(&name) {
+                        info.thunderstore = Some(format!("https://thunderstore.io/c/{}/", identifier));
+                        break;
+                    }
+                }
+            }
+        }
+    }
+
+    // 2. Nexus Mods (FIXED & ROBUST)
+    let slug = title.to_lowercase()
+        .replace(":", "").replace("'", "").replace("-", "")
+        .replace(" ", "").replace("™", "").replace("®", "").replace(".", "");
+        
+    let nexus_url = format!("https://www.nexusmods.com/{}", slug);
+    
+    // On se fait passer pour un vrai navigateur Chrome Windows pour éviter les blocages
+    let agent = ureq::AgentBuilder::new()
+        .timeout(std::time::Duration::from_secs(5))
+        .redirects(10)
+        .build();
+
+    let resp = agent.get(&nexus_url)
+        .set("User-Agent", "Mozilla/5.0 (Windows NT 10.0; Win64; x64) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/120.0.0.0 Safari/537.36")
+        .call();
+
+    match resp {
+        Ok(r) => {
+            // Si le site répond 200 OK, c'est bon
+            if r.status() == 200 {
+                info.nexus = Some(nexus_url);
+            }
+        },
+        Err(ureq::Error::Status(404, _)) => {
+            // Si c'est une vraie 404, le jeu n'existe pas sur Nexus -> Pas de bouton
+            info.nexus = None;
+        },
+        Err(_) => {
+            // Pour TOUTE autre erreur (Timeout, Réseau, 429 Too Many Requests...)
+            // On affiche le bouton de manière OPTIMISTE.
+            // Cela règle le problème "une fois sur deux" : si ça lag, le bouton sera là quand même.
+            info.nexus = Some(nexus_url);
+        }
+    }
+
+    info
+}
@@ -22,7 +22,10 @@ register('fr', () => Promise.resolve({
     "folder": "Dossier",
     "open": "Ouvrir",
     "details": "Détails",
-    "favorite": "Favori"
+    "favorite": "Favori",
+    "state_downloading": "Téléchargement {percent}%",
+    "state_update_required": "Mise à jour requise",
+    "state_uninstalled": "Désinstallé"
   },
   "modal_add": {
     "title": "AJOUTER UN JEU",
@@ -33,7 +36,12 @@ register('fr', () => Promise.resolve({
     "label_img": "Image (Optionnel)",
     "placeholder_img": "Chemin de l'image...",
     "cancel": "Annuler",
-    "confirm": "Ajouter"
+    "confirm": "Ajouter",
+    "import_shortcuts": "Importer des raccourcis (.lnk / .url)...",
+    "add_selected": "Ajouter la sélection",
+    "deep_scan": "Chercher des jeux sur les disques",
+    "deep_scan_running": "Recherche en cours...",
+    "no_proposals": "Aucun nouveau jeu trouvé."
   },
   "settings": {
     "title": "Paramètres",
@@ -47,9 +55,21 @@ register('fr', () => Promise.resolve({
     "general": {
       "autostart_title": "Lancement automatique",
       "autostart_desc": "Démarrer Geewer GameHub avec le système",
+      "tools_title": "Afficher les outils",
+      "tools_desc": "Proton, runtimes Steam, redistribuables et SDK",
       "drives_title": "Disques à scanner",
       "drives_desc": "Sélectionnez les lecteurs où vos jeux sont installés pour optimiser le scan.",
-      "no_drives": "Aucun disque détecté automatiquement."
+      "no_drives": "Aucun disque détecté automatiquement.",
+      "emulators_title": "Émulateurs",
+      "emulators_desc": "Chaque ROM du dossier devient un jeu. Dans la commande, '{rom}' est remplacé par le chemin de la ROM.",
+      "emulator_name": "Système (ex: SNES)",
+      "emulator_folder": "Dossier des ROMs",
+      "emulator_extensions": "Extensions (ex: sfc, smc, zip)",
+      "emulator_command": "Commande (ex: retroarch -L snes9x_libretro.so '{rom}')",
+      "emulator_add": "Ajouter un système",
+      "deep_scan_title": "Scan profond",
+      "deep_scan_desc": "Jeux sans launcher (dossiers DRM-free) : la recherche depuis \"Ajouter un jeu\" parcourt ces dossiers, ou à défaut les disques sélectionnés.",
+      "deep_scan_add": "Ajouter un dossier"
     },
     "appearance": {
       "presets_title": "Thèmes Prédéfinis",
@@ -119,7 +139,10 @@ register('en', () => Promise.resolve({
     "folder": "Folder",
     "open": "Open",
     "details": "Details",
-    "favorite": "Favorite"
+    "favorite": "Favorite",
+    "state_downloading": "Downloading {percent}%",
+    "state_update_required": "Update required",
+    "state_uninstalled": "Uninstalled"
   },
   "modal_add": {
     "title": "ADD A GAME",
@@ -130,7 +153,12 @@ register('en', () => Promise.resolve({
     "label_img": "Image (Optional)",
     "placeholder_img": "Image path...",
     "cancel": "Cancel",
-    "confirm": "Add"
+    "confirm": "Add",
+    "import_shortcuts": "Import shortcuts (.lnk / .url)...",
+    "add_selected": "Add selected",
+    "deep_scan": "Search drives for games",
+    "deep_scan_running": "Searching...",
+    "no_proposals": "No new games found."
   },
   "settings": {
     "title": "Settings",
@@ -144,9 +172,21 @@ register('en', () => Promise.resolve({
     "general": {
       "autostart_title": "Auto-start",
       "autostart_desc": "Start Geewer GameHub with Windows",
+      "tools_title": "Show tools",
+      "tools_desc": "Proton, Steam runtimes, redistributables and SDKs",
       "drives_title": "Drives to scan",
       "drives_desc": "Select drives where your games are installed to optimize scanning.",
-      "no_drives": "No drives detected automatically."
+      "no_drives": "No drives detected automatically.",
+      "emulators_title": "Emulators",
+      "emulators_desc": "Every ROM in the folder becomes a game. In the command, '{rom}' is replaced with the ROM path.",
+      "emulator_name": "System (e.g. SNES)",
+      "emulator_folder": "ROM folder",
+      "emulator_extensions": "Extensions (e.g. sfc, smc, zip)",
+      "emulator_command": "Command (e.g. retroarch -L snes9x_libretro.so '{rom}')",
+      "emulator_add": "Add a system",
+      "deep_scan_title": "Deep scan",
+      "deep_scan_desc": "Games without a launcher (DRM-free folders): the search from \"Add a game\" walks these folders, or the selected drives if none.",
+      "deep_scan_add": "Add a folder"
     },
     "appearance": {
       "presets_title": "Preset Themes",